@@ -19,3 +19,32 @@ pub const EXPLORATION_RATE: f64 = 0.1;
 
 /// The default number of iterations before improvement.
 pub const ITERATIONS_BEFORE_IMPROVEMENT: Option<u32> = None;
+
+/// The default tolerance under which an iterative algorithm is considered converged.
+pub const TOLERANCE: f64 = 1e-5;
+
+/// The default maximum number of iterations of an iterative algorithm.
+pub const MAX_NUM_ITERATIONS: u32 = 10_000;
+
+/// The default number of transitions per replayed minibatch.
+pub const BATCH_SIZE: usize = 32;
+
+/// The default number of replayed minibatches per environment step.
+pub const REPLAY_RATIO: u32 = 1;
+
+/// The default learning rate of the secondary weights,
+/// such as the auxiliary weights of gradient temporal difference methods
+/// or the critic of actor-critic methods.
+pub const SECONDARY_LEARNING_RATE: f64 = 0.1;
+
+/// The default decay rate of eligibility traces.
+pub const TRACE_DECAY: f64 = 0.9;
+
+/// The default temperature of entropy-regularized algorithms.
+pub const TEMPERATURE: f64 = 1.0;
+
+/// The default number of simulations of tree search planners.
+pub const NUM_SIMULATIONS: u32 = 1_000;
+
+/// The default exploration constant of tree search planners.
+pub const EXPLORATION_CONSTANT: f64 = std::f64::consts::SQRT_2;
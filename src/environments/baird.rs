@@ -0,0 +1,170 @@
+//! # baird
+//!
+//! Baird's counterexample is a continuing environment with seven states and two actions.
+//! The dashed action leads to one of the first six states uniformly at random,
+//! while the solid action always leads to the seventh state.
+//! All rewards are zero, so that the true value of every state is zero.
+//! With the linear features below, off-policy semi-gradient temporal difference methods
+//! diverge when learning the value of the target policy, which always takes the solid action,
+//! from the behaviour policy, which takes the dashed action six times out of seven.
+
+use rand::prelude::*;
+
+use crate::models::{
+    Action, Environment, FeatureVector, Sampler, State, StateFeatures, StochasticPolicy,
+};
+
+const NUM_STATES: usize = 7;
+const NUM_FEATURES: usize = 8;
+
+/// A state of Baird's counterexample, identified by its index
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct BairdState(pub usize);
+
+impl State for BairdState {}
+
+/// An action of Baird's counterexample
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub enum BairdAction {
+    Dashed,
+    Solid,
+}
+
+impl Action for BairdAction {}
+
+/// The linear features of Baird's counterexample:
+/// the value of each of the first six states is twice its own weight plus the shared eighth weight,
+/// and the value of the seventh state is its own weight plus twice the shared eighth weight
+#[derive(Debug, Clone)]
+pub struct BairdFeatures;
+
+impl StateFeatures<BairdState> for BairdFeatures {
+    fn get_num_features(&self) -> usize {
+        NUM_FEATURES
+    }
+
+    fn get_features(&self, state: &BairdState) -> FeatureVector {
+        if state.0 < NUM_STATES - 1 {
+            FeatureVector::Sparse(vec![(state.0, 2.0), (NUM_FEATURES - 1, 1.0)])
+        } else {
+            FeatureVector::Sparse(vec![(state.0, 1.0), (NUM_FEATURES - 1, 2.0)])
+        }
+    }
+}
+
+/// Baird's counterexample environment,
+/// whose episodes start from a state sampled uniformly at random
+/// and never end unless truncated
+pub struct BairdCounterexample {
+    states: Sampler<BairdState>,
+    actions: Sampler<BairdAction>,
+    state: Option<BairdState>,
+}
+
+impl BairdCounterexample {
+    /// Creates a new Baird's counterexample environment
+    pub fn new() -> Self {
+        Self {
+            states: (0..NUM_STATES).map(BairdState).collect::<Vec<_>>().into(),
+            actions: vec![BairdAction::Dashed, BairdAction::Solid].into(),
+            state: None,
+        }
+    }
+
+    /// Returns a reference to the state sampler
+    pub fn get_states(&self) -> &Sampler<BairdState> {
+        &self.states
+    }
+
+    /// Returns the weights from which the naive methods diverge
+    pub fn get_initial_weights(&self) -> Vec<f64> {
+        vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 10.0, 1.0]
+    }
+
+    /// Returns the target policy, which always takes the solid action
+    pub fn get_target_policy(&self) -> StochasticPolicy<BairdState, BairdAction> {
+        let mut policy = StochasticPolicy::new(&self.states, &self.actions);
+        for state in &self.states {
+            policy.insert(state, &BairdAction::Dashed, 0.0);
+            policy.insert(state, &BairdAction::Solid, 1.0);
+        }
+        policy
+    }
+
+    /// Returns the behaviour policy, which takes the dashed action six times out of seven
+    pub fn get_behaviour_policy(&self) -> StochasticPolicy<BairdState, BairdAction> {
+        let mut policy = StochasticPolicy::new(&self.states, &self.actions);
+        for state in &self.states {
+            policy.insert(state, &BairdAction::Dashed, 6.0 / 7.0);
+            policy.insert(state, &BairdAction::Solid, 1.0 / 7.0);
+        }
+        policy
+    }
+}
+
+impl Default for BairdCounterexample {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Environment for BairdCounterexample {
+    type State = BairdState;
+    type Action = BairdAction;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        &self.actions
+    }
+
+    fn reset(&mut self) -> Self::State {
+        let state = self.states.get_random().clone();
+        self.state = Some(state.clone());
+        state
+    }
+
+    fn step(&mut self, action: &Self::Action) -> (Self::State, f64, bool, bool) {
+        assert!(
+            self.state.is_some(),
+            "environment must be reset before stepping."
+        );
+        let next_state = match action {
+            BairdAction::Dashed => BairdState(thread_rng().gen_range(0..NUM_STATES - 1)),
+            BairdAction::Solid => BairdState(NUM_STATES - 1),
+        };
+        self.state = Some(next_state.clone());
+        (next_state, 0.0, false, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions() {
+        let mut env = BairdCounterexample::new();
+        env.reset();
+        let (state, reward, done, truncated) = env.step(&BairdAction::Solid);
+        assert_eq!(state, BairdState(6));
+        assert_eq!(reward, 0.0);
+        assert!(!done && !truncated);
+        for _ in 0..100 {
+            let (state, _, _, _) = env.step(&BairdAction::Dashed);
+            assert!(state.0 < 6);
+        }
+    }
+
+    #[test]
+    fn initial_values() {
+        let env = BairdCounterexample::new();
+        let weights = env.get_initial_weights();
+        assert_eq!(
+            BairdFeatures.get_features(&BairdState(0)).dot(&weights),
+            3.0
+        );
+        assert_eq!(
+            BairdFeatures.get_features(&BairdState(6)).dot(&weights),
+            12.0
+        );
+    }
+}
@@ -1,11 +1,27 @@
 /// # bandit
 ///
-/// This module contains a test bandit model.
+/// This module contains test bandit models.
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+};
+
 use rand::Rng;
-use rand_distr::StandardNormal;
+use rand_distr::{Bernoulli, Distribution, Normal, StandardNormal};
+
+use crate::models::{Action, Bandit, ContextualBandit, Sampler};
+
+/// A bandit arm, identified by its index
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Arm(pub usize);
 
+impl Action for Arm {}
+
+/// A k-armed bandit whose arms give rewards
+/// drawn from a normal distribution with unit variance
 pub struct KArmedBandit {
     arm_values: Vec<f64>,
+    actions: Sampler<Arm>,
 }
 
 impl KArmedBandit {
@@ -17,7 +33,16 @@ impl KArmedBandit {
             let value = rng.sample(StandardNormal);
             arm_values.push(value);
         }
-        KArmedBandit { arm_values }
+        Self::from_arm_values(arm_values)
+    }
+
+    /// Create a new bandit with the specified arm values
+    pub fn from_arm_values(arm_values: Vec<f64>) -> Self {
+        let actions = (0..arm_values.len()).map(Arm).collect::<Vec<_>>().into();
+        KArmedBandit {
+            arm_values,
+            actions,
+        }
     }
 
     /// Sample the value of an arm
@@ -27,6 +52,353 @@ impl KArmedBandit {
     }
 }
 
+impl Bandit for KArmedBandit {
+    type Action = Arm;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        &self.actions
+    }
+
+    fn reward(&self, action: &Self::Action) -> f64 {
+        self.sample_arm(action.0 as i32)
+    }
+
+    fn get_mean(&self, action: &Self::Action) -> Option<f64> {
+        Some(self.arm_values[action.0])
+    }
+}
+
+/// The way the arm values of a non-stationary bandit change after each pull
+#[derive(Debug, Clone)]
+pub enum Drift {
+    /// Each arm value takes an independent normal increment
+    /// with the given standard deviation
+    RandomWalk(f64),
+    /// With the given probability, all arm values
+    /// are redrawn from a standard normal distribution
+    ChangePoint(f64),
+}
+
+/// A k-armed bandit whose arm values drift over time
+/// and whose arms give rewards drawn from a normal distribution with unit variance.
+/// The arm values are kept in a `RefCell` so that they can drift after each pull
+pub struct NonStationaryBandit {
+    arm_values: RefCell<Vec<f64>>,
+    actions: Sampler<Arm>,
+    drift: Drift,
+}
+
+impl NonStationaryBandit {
+    /// Create a new non-stationary k-armed bandit
+    /// whose initial arm values are drawn from a standard normal distribution
+    pub fn new(k: i32, drift: Drift) -> Self {
+        let mut rng = rand::thread_rng();
+        let arm_values = (0..k).map(|_| rng.sample(StandardNormal)).collect();
+        Self::from_arm_values(arm_values, drift)
+    }
+
+    /// Create a new non-stationary bandit with the specified initial arm values
+    pub fn from_arm_values(arm_values: Vec<f64>, drift: Drift) -> Self {
+        let actions = (0..arm_values.len()).map(Arm).collect::<Vec<_>>().into();
+        NonStationaryBandit {
+            arm_values: RefCell::new(arm_values),
+            actions,
+            drift,
+        }
+    }
+
+    /// Apply the drift to the arm values
+    fn drift(&self) {
+        let mut rng = rand::thread_rng();
+        let mut arm_values = self.arm_values.borrow_mut();
+        match self.drift {
+            Drift::RandomWalk(standard_deviation) => {
+                // unwrap is safe as long as the standard deviation is not negative
+                let increment = Normal::new(0.0, standard_deviation).unwrap();
+                for value in arm_values.iter_mut() {
+                    *value += rng.sample(increment);
+                }
+            }
+            Drift::ChangePoint(probability) => {
+                if rng.gen::<f64>() < probability {
+                    for value in arm_values.iter_mut() {
+                        *value = rng.sample(StandardNormal);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Bandit for NonStationaryBandit {
+    type Action = Arm;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        &self.actions
+    }
+
+    fn reward(&self, action: &Self::Action) -> f64 {
+        let reward = self.arm_values.borrow()[action.0]
+            + rand::thread_rng().sample::<f64, StandardNormal>(StandardNormal);
+        self.drift();
+        reward
+    }
+
+    fn get_mean(&self, action: &Self::Action) -> Option<f64> {
+        Some(self.arm_values.borrow()[action.0])
+    }
+}
+
+/// The reward distribution of a bandit arm
+pub enum ArmDistribution {
+    /// Gives a reward of one with the given probability and zero otherwise
+    Bernoulli(f64),
+    /// Gives rewards drawn from a normal distribution
+    /// with the given mean and standard deviation
+    Gaussian(f64, f64),
+    /// Gives rewards drawn from any distribution, with an optionally known mean
+    Custom(Box<dyn Fn() -> f64>, Option<f64>),
+}
+
+impl ArmDistribution {
+    /// Create an arm from any distribution, such as the ones of `rand_distr`,
+    /// with an optionally known mean
+    pub fn custom<D>(distribution: D, mean: Option<f64>) -> Self
+    where
+        D: Distribution<f64> + 'static,
+    {
+        Self::Custom(
+            Box::new(move || distribution.sample(&mut rand::thread_rng())),
+            mean,
+        )
+    }
+
+    /// Sample a reward from the distribution
+    pub fn sample(&self) -> f64 {
+        let mut rng = rand::thread_rng();
+        match self {
+            Self::Bernoulli(probability) => {
+                // unwrap is safe as long as the probability is between zero and one
+                let bernoulli = Bernoulli::new(*probability).unwrap();
+                if rng.sample(bernoulli) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Gaussian(mean, standard_deviation) => {
+                // unwrap is safe as long as the standard deviation is not negative
+                rng.sample(Normal::new(*mean, *standard_deviation).unwrap())
+            }
+            Self::Custom(sample, _) => sample(),
+        }
+    }
+
+    /// Get the mean of the distribution if it is known
+    pub fn get_mean(&self) -> Option<f64> {
+        match self {
+            Self::Bernoulli(probability) => Some(*probability),
+            Self::Gaussian(mean, _) => Some(*mean),
+            Self::Custom(_, mean) => *mean,
+        }
+    }
+}
+
+impl fmt::Debug for ArmDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bernoulli(probability) => write!(f, "Bernoulli({})", probability),
+            Self::Gaussian(mean, standard_deviation) => {
+                write!(f, "Gaussian({}, {})", mean, standard_deviation)
+            }
+            Self::Custom(_, mean) => write!(f, "Custom({:?})", mean),
+        }
+    }
+}
+
+/// A bandit whose arms each have their own reward distribution
+pub struct DistributionBandit {
+    arms: Vec<ArmDistribution>,
+    actions: Sampler<Arm>,
+}
+
+impl DistributionBandit {
+    /// Create a new bandit with the specified arm distributions
+    pub fn new(arms: Vec<ArmDistribution>) -> Self {
+        let actions = (0..arms.len()).map(Arm).collect::<Vec<_>>().into();
+        DistributionBandit { arms, actions }
+    }
+
+    /// Create a new bandit with Bernoulli arms of the specified probabilities
+    pub fn bernoulli(probabilities: Vec<f64>) -> Self {
+        Self::new(
+            probabilities
+                .into_iter()
+                .map(ArmDistribution::Bernoulli)
+                .collect(),
+        )
+    }
+}
+
+impl Bandit for DistributionBandit {
+    type Action = Arm;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        &self.actions
+    }
+
+    fn reward(&self, action: &Self::Action) -> f64 {
+        self.arms[action.0].sample()
+    }
+
+    fn get_mean(&self, action: &Self::Action) -> Option<f64> {
+        self.arms[action.0].get_mean()
+    }
+}
+
+/// A bandit whose rewards are chosen by an adversary
+/// and supplied upfront as a sequence with one reward per arm for each step.
+/// The sequence is repeated once it is exhausted.
+/// The bandit tracks the regret of the pulled arms
+/// against the best fixed arm in hindsight,
+/// in `Cell`s so that it can be updated after each pull.
+pub struct AdversarialBandit {
+    rewards: Vec<Vec<f64>>,
+    actions: Sampler<Arm>,
+    num_steps: Cell<usize>,
+    arm_totals: RefCell<Vec<f64>>,
+    total: Cell<f64>,
+}
+
+impl AdversarialBandit {
+    /// Create a new adversarial bandit with the specified reward sequence,
+    /// where `rewards[t][i]` is the reward of arm `i` at step `t`
+    pub fn new(rewards: Vec<Vec<f64>>) -> Self {
+        assert!(!rewards.is_empty(), "reward sequence must not be empty.");
+        let k = rewards[0].len();
+        assert!(
+            rewards.iter().all(|step| step.len() == k),
+            "every step must give a reward to each arm."
+        );
+        let actions = (0..k).map(Arm).collect::<Vec<_>>().into();
+        AdversarialBandit {
+            rewards,
+            actions,
+            num_steps: Cell::new(0),
+            arm_totals: RefCell::new(vec![0.0; k]),
+            total: Cell::new(0.0),
+        }
+    }
+
+    /// Get the regret of the pulled arms against the best fixed arm in hindsight
+    pub fn get_regret(&self) -> f64 {
+        let best_total = self
+            .arm_totals
+            .borrow()
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        best_total - self.total.get()
+    }
+}
+
+impl Bandit for AdversarialBandit {
+    type Action = Arm;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        &self.actions
+    }
+
+    fn reward(&self, action: &Self::Action) -> f64 {
+        let num_steps = self.num_steps.get();
+        let step = &self.rewards[num_steps % self.rewards.len()];
+        for (total, reward) in self.arm_totals.borrow_mut().iter_mut().zip(step) {
+            *total += reward;
+        }
+        self.total.set(self.total.get() + step[action.0]);
+        self.num_steps.set(num_steps + 1);
+        step[action.0]
+    }
+}
+
+/// A contextual bandit whose contexts are drawn from a standard normal distribution
+/// and whose mean rewards are linear in the context,
+/// with one parameter vector per arm and normal noise
+pub struct LinearContextualBandit {
+    parameters: Vec<Vec<f64>>,
+    noise: f64,
+    actions: Sampler<Arm>,
+    context: Vec<f64>,
+}
+
+impl LinearContextualBandit {
+    /// Create a new linear contextual bandit with k arms and d features
+    /// whose parameters are drawn from a standard normal distribution
+    pub fn new(k: usize, d: usize, noise: f64) -> Self {
+        let mut rng = rand::thread_rng();
+        let parameters = (0..k)
+            .map(|_| (0..d).map(|_| rng.sample(StandardNormal)).collect())
+            .collect();
+        Self::from_parameters(parameters, noise)
+    }
+
+    /// Create a new linear contextual bandit with the specified arm parameters
+    /// and noise standard deviation
+    pub fn from_parameters(parameters: Vec<Vec<f64>>, noise: f64) -> Self {
+        let d = parameters[0].len();
+        assert!(
+            parameters.iter().all(|parameter| parameter.len() == d),
+            "every arm must have the same number of parameters."
+        );
+        let actions = (0..parameters.len()).map(Arm).collect::<Vec<_>>().into();
+        LinearContextualBandit {
+            parameters,
+            noise,
+            actions,
+            context: vec![0.0; d],
+        }
+    }
+
+    /// Get the mean reward of an arm in the current context
+    fn get_arm_mean(&self, arm: usize) -> f64 {
+        self.parameters[arm]
+            .iter()
+            .zip(&self.context)
+            .map(|(parameter, feature)| parameter * feature)
+            .sum()
+    }
+}
+
+impl ContextualBandit for LinearContextualBandit {
+    type Action = Arm;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        &self.actions
+    }
+
+    fn get_num_features(&self) -> usize {
+        self.context.len()
+    }
+
+    fn next_context(&mut self) -> Vec<f64> {
+        let mut rng = rand::thread_rng();
+        for feature in self.context.iter_mut() {
+            *feature = rng.sample(StandardNormal);
+        }
+        self.context.clone()
+    }
+
+    fn reward(&mut self, action: &Self::Action) -> f64 {
+        // unwrap is safe as long as the noise is not negative
+        let noise = Normal::new(0.0, self.noise).unwrap();
+        self.get_arm_mean(action.0) + rand::thread_rng().sample(noise)
+    }
+
+    fn get_mean(&self, action: &Self::Action) -> Option<f64> {
+        Some(self.get_arm_mean(action.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,6 +407,7 @@ mod tests {
     fn test_bandit() {
         let bandit = KArmedBandit::new(10);
         assert_eq!(bandit.arm_values.len(), 10);
+        assert_eq!(bandit.get_actions().iter().count(), 10);
     }
 
     #[test]
@@ -43,4 +416,68 @@ mod tests {
         let value = bandit.sample_arm(0);
         assert!(value.is_finite());
     }
+
+    #[test]
+    fn test_reward() {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 1.0]);
+        assert!(bandit.reward(&Arm(1)).is_finite());
+        assert_eq!(bandit.get_mean(&Arm(1)), Some(1.0));
+    }
+
+    #[test]
+    fn test_bernoulli_arms() {
+        let bandit = DistributionBandit::bernoulli(vec![0.0, 1.0]);
+        assert_eq!(bandit.reward(&Arm(0)), 0.0);
+        assert_eq!(bandit.reward(&Arm(1)), 1.0);
+        assert_eq!(bandit.get_mean(&Arm(1)), Some(1.0));
+    }
+
+    #[test]
+    fn test_custom_arms() {
+        let bandit = DistributionBandit::new(vec![
+            ArmDistribution::Gaussian(2.0, 0.0),
+            ArmDistribution::custom(rand_distr::Uniform::new(3.0, 4.0), None),
+        ]);
+        assert_eq!(bandit.reward(&Arm(0)), 2.0);
+        assert!((3.0..4.0).contains(&bandit.reward(&Arm(1))));
+        assert_eq!(bandit.get_mean(&Arm(1)), None);
+    }
+
+    #[test]
+    fn test_adversarial_regret() {
+        let bandit = AdversarialBandit::new(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, 1.0]]);
+        assert_eq!(bandit.reward(&Arm(0)), 1.0);
+        assert_eq!(bandit.reward(&Arm(0)), 0.0);
+        assert_eq!(bandit.reward(&Arm(0)), 0.0);
+        assert_eq!(bandit.reward(&Arm(0)), 1.0);
+        assert_eq!(bandit.get_regret(), 0.0);
+        bandit.reward(&Arm(0));
+        assert_eq!(bandit.get_regret(), 1.0);
+    }
+
+    #[test]
+    fn test_linear_contextual_bandit() {
+        let mut bandit = LinearContextualBandit::from_parameters(vec![vec![1.0, 0.0]], 0.0);
+        let context = bandit.next_context();
+        assert_eq!(bandit.get_num_features(), 2);
+        assert_eq!(bandit.reward(&Arm(0)), context[0]);
+        assert_eq!(bandit.get_mean(&Arm(0)), Some(context[0]));
+    }
+
+    #[test]
+    fn test_random_walk() {
+        let bandit = NonStationaryBandit::from_arm_values(vec![0.0], Drift::RandomWalk(1.0));
+        bandit.reward(&Arm(0));
+        assert_ne!(bandit.get_mean(&Arm(0)), Some(0.0));
+    }
+
+    #[test]
+    fn test_change_point() {
+        let bandit = NonStationaryBandit::from_arm_values(vec![5.0], Drift::ChangePoint(0.0));
+        bandit.reward(&Arm(0));
+        assert_eq!(bandit.get_mean(&Arm(0)), Some(5.0));
+        let bandit = NonStationaryBandit::from_arm_values(vec![5.0], Drift::ChangePoint(1.0));
+        bandit.reward(&Arm(0));
+        assert_ne!(bandit.get_mean(&Arm(0)), Some(5.0));
+    }
 }
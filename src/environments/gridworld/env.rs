@@ -1,3 +1,5 @@
+use rand::prelude::*;
+
 use crate::models::{Action, Sampler, State, MDP};
 
 use super::{END_TRANSITION_REWARD, NO_OP_TRANSITION_REWARD};
@@ -42,6 +44,7 @@ pub struct Gridworld {
     cell_grid: Vec<Vec<Cell>>,
     states: Sampler<GridworldState>,
     actions: Sampler<GridworldAction>,
+    initial_states: Option<Sampler<GridworldState>>,
 }
 
 impl Gridworld {
@@ -55,9 +58,22 @@ impl Gridworld {
             cell_grid,
             states: states.into(),
             actions: actions.into(),
+            initial_states: None,
         }
     }
 
+    /// Sets the states from which episodes start and returns the gridworld.
+    /// By default, episodes start from a random air cell.
+    pub fn initial_states(mut self, initial_states: Vec<GridworldState>) -> Self {
+        self.initial_states = Some(initial_states.into());
+        self
+    }
+
+    /// Returns the cell at the given state
+    fn get_cell(&self, state: &GridworldState) -> &Cell {
+        &self.cell_grid[state.i][state.j]
+    }
+
     /// Returns the grid's width and height
     fn get_grid_size(&self) -> (usize, usize) {
         (self.cell_grid.len(), self.cell_grid[0].len())
@@ -77,12 +93,11 @@ impl MDP for Gridworld {
     }
 
     fn is_state_terminal(&self, state: &Self::State) -> bool {
-        let cell = &self.cell_grid[state.i][state.j];
-        *cell == Cell::End
+        *self.get_cell(state) == Cell::End
     }
 
     fn transition(&self, state: &Self::State, action: &Self::Action) -> (Self::State, f64) {
-        let cell = &self.cell_grid[state.i][state.j];
+        let cell = self.get_cell(state);
 
         // Edge cases
         // In theory the Cell::Wall case should never happen
@@ -115,6 +130,18 @@ impl MDP for Gridworld {
             Cell::End => (Self::State::new(i_, j_), END_TRANSITION_REWARD),
         }
     }
+
+    fn get_initial_state(&self) -> Self::State {
+        if let Some(initial_states) = &self.initial_states {
+            return initial_states.get_random().clone();
+        }
+        self.states
+            .iter()
+            .filter(|state| *self.get_cell(state) == Cell::Air)
+            .choose(&mut thread_rng())
+            .expect("gridworld must contain at least one air cell.")
+            .clone()
+    }
 }
 
 #[cfg(test)]
@@ -173,6 +200,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn initial_state_is_air() {
+        let mdp = get_gridworld();
+        for _ in 0..100 {
+            let state = mdp.get_initial_state();
+            assert!(state == TOP_LEFT || state == TOP_RIGHT);
+        }
+    }
+
+    #[test]
+    fn custom_initial_states() {
+        let mdp = get_gridworld().initial_states(vec![TOP_RIGHT.clone()]);
+        assert_eq!(mdp.get_initial_state(), TOP_RIGHT);
+    }
+
     #[test]
     fn transition_from_terminal() {
         let mdp = get_gridworld();
@@ -28,6 +28,25 @@ pub fn get_gridworld() -> Gridworld {
     )
 }
 
+#[doc(hidden)]
+pub fn get_maze() -> Gridworld {
+    let cell_grid = vec![
+        vec![Cell::Air, Cell::Air, Cell::Air, Cell::Air],
+        vec![Cell::Air, Cell::Wall, Cell::Wall, Cell::Air],
+        vec![Cell::Air, Cell::Air, Cell::Wall, Cell::Air],
+        vec![Cell::Wall, Cell::Air, Cell::Air, Cell::End],
+    ];
+    let mut states = vec![];
+    for (i, row) in cell_grid.iter().enumerate() {
+        for (j, cell) in row.iter().enumerate() {
+            if *cell != Cell::Wall {
+                states.push(GridworldState::new(i, j));
+            }
+        }
+    }
+    Gridworld::new(cell_grid, states, get_actions())
+}
+
 #[doc(hidden)]
 pub fn get_test_config() -> Config {
     Config::new()
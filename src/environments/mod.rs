@@ -2,7 +2,8 @@
 //!
 //! This module contains classical example environments
 //! that can be used in examples and tests.
-//! It currently only contains the Gridworld environment.
+//! It contains bandits, the Gridworld environment and Baird's counterexample.
 
+pub mod baird;
 pub mod bandit;
 pub mod gridworld;
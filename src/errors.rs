@@ -1,17 +1,21 @@
 //! # errors
 //!
 //! This module contains the error messages for the library.
-//! It currently only contains the `NotFound` enum.
+//! It contains the `NotFound` enum, whose messages are used in panics,
+//! and the `SSPError` enum, returned by the stochastic shortest path solvers.
 
-use std::fmt;
+use std::{error, fmt};
 
 /// The `NotFound` enum contains the error messages
-/// for when a state or action is not found as a key in a map.
+/// for when a state or action is not found as a key in a map
+/// or in a feature extractor.
 pub enum NotFound {
     StateInPolicy,
     StateInStateValue,
     StateInActionValue,
     ActionInStateActionValue,
+    StateInFeatures,
+    ActionInFeatures,
 }
 
 impl fmt::Display for NotFound {
@@ -21,7 +25,37 @@ impl fmt::Display for NotFound {
             NotFound::StateInStateValue => "state not found in state value",
             NotFound::StateInActionValue => "state not found in action value",
             NotFound::ActionInStateActionValue => "action not found in state action value",
+            NotFound::StateInFeatures => "state not found in features",
+            NotFound::ActionInFeatures => "action not found in features",
         };
         write!(f, "{}", message)
     }
 }
+
+/// The `SSPError` enum contains the errors
+/// returned by the stochastic shortest path solvers,
+/// which require the policies to reach a terminal state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SSPError {
+    /// The policy does not reach a terminal state from some state.
+    ImproperPolicy,
+    /// No policy reaches a terminal state from some state.
+    NoProperPolicy,
+    /// A cycle with a positive reward makes the optimal return unbounded.
+    UnboundedReturn,
+}
+
+impl fmt::Display for SSPError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            SSPError::ImproperPolicy => "policy does not reach a terminal state from every state",
+            SSPError::NoProperPolicy => "no policy reaches a terminal state from every state",
+            SSPError::UnboundedReturn => {
+                "return is unbounded because of a cycle with a positive reward"
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl error::Error for SSPError {}
@@ -21,13 +21,28 @@
 //! - [Value Iteration](https://en.wikipedia.org/wiki/Markov_decision_process#Value_iteration)
 //! - [SARSA](https://en.wikipedia.org/wiki/State-Action-Reward-State-Action)
 //! - [Q-Learning](https://en.wikipedia.org/wiki/Q-learning)
+//! - Fitted Q Iteration and Batch Q-Learning from a fixed dataset
+//! - Multi-armed bandits: epsilon-greedy sample average, [UCB1](https://en.wikipedia.org/wiki/Multi-armed_bandit#Approximate_solutions), gradient bandit and Gaussian and Beta-Bernoulli [Thompson Sampling](https://en.wikipedia.org/wiki/Thompson_sampling)
+//! - Non-stationary bandits: constant step size, discounted UCB and sliding window UCB
+//! - Adversarial bandits: [EXP3 and EXP3.P](https://en.wikipedia.org/wiki/Multi-armed_bandit#Exp3)
+//! - Contextual bandits: LinUCB and linear Thompson sampling
+//! - Best-arm identification: successive elimination, LUCB and sequential halving
+//! - Linear function approximation: semi-gradient TD(0), SARSA and Q-Learning
+//! - Features for continuous states: [tile coding](https://en.wikipedia.org/wiki/Tile_coding), radial basis functions, Fourier and polynomial bases and state aggregation
+//! - Gradient temporal difference methods for stable off-policy learning: GTD2, TDC and Greedy-GQ
+//! - Policy gradient: [REINFORCE](https://en.wikipedia.org/wiki/Policy_gradient_method#REINFORCE) with and without baseline, one-step actor-critic and actor-critic with eligibility traces
+//! - Entropy-regularized (maximum entropy) control: soft value iteration and soft Q-Learning
+//! - Inverse reinforcement learning: maximum entropy IRL and apprenticeship learning via feature expectation matching
+//! - Online planning: [Monte Carlo tree search](https://en.wikipedia.org/wiki/Monte_Carlo_tree_search) with UCT
+//! - Heuristic search: real-time dynamic programming (RTDP) and labeled RTDP, LAO* and ILAO*, [A*](https://en.wikipedia.org/wiki/A*_search_algorithm) and Dijkstra
+//! - Stochastic shortest path: proper policy detection, undiscounted policy evaluation and value iteration
+//! - Model-based exploration: R-max and UCRL2 with optimistic planning on learned models
 //!
 //! ## Roadmap
 //!
 //! The following features are planned for the future:
 //!
 //! - Stochastic MDPs
-//! - Stochastic policies
 //! - State-dependent action spaces
 //! - More algorithms
 
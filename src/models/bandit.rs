@@ -12,5 +12,43 @@ pub trait Bandit {
     fn get_actions(&self) -> &Sampler<Self::Action>;
 
     /// Given an action, returns the reward.
+    /// A bandit whose state changes with each pull,
+    /// for instance when its arm values drift over time,
+    /// can keep that state in a [`Cell`](std::cell::Cell) or a [`RefCell`](std::cell::RefCell).
     fn reward(&self, action: &Self::Action) -> f64;
+
+    /// Returns the current true mean reward of the given action if it is known.
+    /// It is used to compute the regret of bandit algorithms.
+    /// Returns `None` by default.
+    fn get_mean(&self, _action: &Self::Action) -> Option<f64> {
+        None
+    }
+}
+
+/// # Contextual Bandit
+///
+/// You have to implement this trait for your own contextual bandit problem.
+/// At each round, the bandit first reveals a context as a feature vector
+/// and then gives a reward for the selected action in that context.
+pub trait ContextualBandit {
+    type Action: Action;
+
+    /// Returns a reference to the action sampler.
+    fn get_actions(&self) -> &Sampler<Self::Action>;
+
+    /// Returns the number of features of the contexts.
+    fn get_num_features(&self) -> usize;
+
+    /// Starts a new round and returns its context.
+    fn next_context(&mut self) -> Vec<f64>;
+
+    /// Given an action, returns the reward in the context of the current round.
+    fn reward(&mut self, action: &Self::Action) -> f64;
+
+    /// Returns the true mean reward of the given action
+    /// in the context of the current round if it is known.
+    /// Returns `None` by default.
+    fn get_mean(&self, _action: &Self::Action) -> Option<f64> {
+        None
+    }
 }
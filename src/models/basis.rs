@@ -0,0 +1,663 @@
+use std::f64::consts::PI;
+
+use super::{FeatureVector, State, StateFeatures};
+
+/// # Continuous State
+///
+/// Represents a state described by real-valued variables, such as a position and a velocity.
+/// You have to implement this trait for your own state type
+/// to use the feature constructions for continuous states.
+/// Since states must implement `Eq` and `Hash`,
+/// you can for instance store the variables with [`f64::to_bits`].
+///
+/// ## Example
+///
+/// ```
+/// use madepro::models::{ContinuousState, State};
+///
+/// #[derive(PartialEq, Eq, Hash, Clone)]
+/// struct Position(u64);
+///
+/// impl State for Position {}
+///
+/// impl ContinuousState for Position {
+///     fn get_variables(&self) -> Vec<f64> {
+///         vec![f64::from_bits(self.0)]
+///     }
+/// }
+/// ```
+pub trait ContinuousState: State {
+    /// Returns the variables describing the state.
+    fn get_variables(&self) -> Vec<f64>;
+}
+
+fn check_bounds(low: &[f64], high: &[f64]) {
+    assert_eq!(
+        low.len(),
+        high.len(),
+        "low and high must have the same length."
+    );
+    assert!(
+        low.iter().zip(high).all(|(low, high)| low < high),
+        "low must be lower than high in every dimension."
+    );
+}
+
+/// Returns the index of the interval of the given width containing the variable,
+/// clamped between zero and the given maximum.
+fn get_interval(variable: f64, low: f64, width: f64, max: usize) -> usize {
+    ((variable - low) / width).floor().clamp(0.0, max as f64) as usize
+}
+
+/// Returns the 64-bit FNV-1a hash of the given bytes.
+/// Unlike the hasher of the standard library, whose algorithm may change between releases,
+/// it always gives the same result, so that learned weights stay valid.
+fn fnv1a<I>(bytes: I) -> u64
+where
+    I: IntoIterator<Item = u8>,
+{
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes.into_iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Returns every vector of the given length with integer entries between zero and `max`.
+fn get_exponents(length: usize, max: u32) -> Vec<Vec<u32>> {
+    (0..length).fold(vec![vec![]], |exponents, _| {
+        exponents
+            .into_iter()
+            .flat_map(|exponent| {
+                (0..=max).map(move |entry| {
+                    let mut exponent = exponent.clone();
+                    exponent.push(entry);
+                    exponent
+                })
+            })
+            .collect()
+    })
+}
+
+/// # Tile Coding
+///
+/// Partitions the state space with several overlapping grids, called tilings,
+/// each offset from the others by a fraction of a tile.
+/// A state activates exactly one tile per tiling,
+/// so that its features are sparse and binary.
+/// The tilings are offset asymmetrically, by `(1, 3, 5, ...)` fractions of a tile,
+/// which generalizes more uniformly than offsetting every dimension equally.
+/// Variables outside of the bounds are clamped to the border tiles.
+/// With hashing, tiles are mapped to a fixed number of features,
+/// which bounds the memory used in high dimensions at the cost of collisions.
+/// The index of a tile is then the FNV-1a hash of its tiling and coordinates,
+/// as little-endian `u64`, modulo the memory size,
+/// which does not depend on the platform or the Rust version.
+#[derive(Debug, Clone)]
+pub struct TileCoding {
+    num_tilings: usize,
+    tiles_per_dimension: usize,
+    low: Vec<f64>,
+    tile_widths: Vec<f64>,
+    memory_size: Option<usize>,
+}
+
+impl TileCoding {
+    /// Creates a new tile coding with the given number of tilings
+    /// and number of tiles per dimension between the given bounds.
+    pub fn new(
+        num_tilings: usize,
+        tiles_per_dimension: usize,
+        low: Vec<f64>,
+        high: Vec<f64>,
+    ) -> Self {
+        assert!(
+            num_tilings > 0,
+            "tile coding must have at least one tiling."
+        );
+        assert!(
+            tiles_per_dimension > 0,
+            "tile coding must have at least one tile per dimension."
+        );
+        check_bounds(&low, &high);
+        let tile_widths = low
+            .iter()
+            .zip(&high)
+            .map(|(low, high)| (high - low) / tiles_per_dimension as f64)
+            .collect();
+        Self {
+            num_tilings,
+            tiles_per_dimension,
+            low,
+            tile_widths,
+            memory_size: None,
+        }
+    }
+
+    /// Hashes the tiles into the given number of features and returns the tile coding.
+    pub fn hashing(mut self, memory_size: usize) -> Self {
+        assert!(memory_size > 0, "memory size must be positive.");
+        self.memory_size = Some(memory_size);
+        self
+    }
+
+    /// Returns the coordinates of the tile activated by the variables in the given tiling.
+    fn get_tile(&self, tiling: usize, variables: &[f64]) -> Vec<usize> {
+        variables
+            .iter()
+            .zip(&self.low)
+            .zip(&self.tile_widths)
+            .enumerate()
+            .map(|(dimension, ((variable, low), width))| {
+                // each tiling is shifted by (2 * dimension + 1) / num_tilings of a tile
+                let shift = (tiling * (2 * dimension + 1)) % self.num_tilings;
+                let offset = shift as f64 / self.num_tilings as f64 * width;
+                // tilings are one tile larger so that shifted tiles still cover the bounds
+                get_interval(variable + offset, *low, *width, self.tiles_per_dimension)
+            })
+            .collect()
+    }
+
+    fn get_num_tiles_per_tiling(&self) -> usize {
+        (self.tiles_per_dimension + 1).pow(self.low.len() as u32)
+    }
+}
+
+impl<S> StateFeatures<S> for TileCoding
+where
+    S: ContinuousState,
+{
+    fn get_num_features(&self) -> usize {
+        self.memory_size
+            .unwrap_or(self.num_tilings * self.get_num_tiles_per_tiling())
+    }
+
+    fn get_features(&self, state: &S) -> FeatureVector {
+        let variables = state.get_variables();
+        let num_tiles = self.get_num_tiles_per_tiling();
+        FeatureVector::Sparse(
+            (0..self.num_tilings)
+                .map(|tiling| {
+                    let tile = self.get_tile(tiling, &variables);
+                    let index = match self.memory_size {
+                        Some(memory_size) => {
+                            // each integer is hashed as eight little-endian bytes
+                            let bytes = std::iter::once(tiling)
+                                .chain(tile)
+                                .flat_map(|value| (value as u64).to_le_bytes());
+                            (fnv1a(bytes) % memory_size as u64) as usize
+                        }
+                        None => {
+                            tiling * num_tiles
+                                + tile.iter().rev().fold(0, |index, coordinate| {
+                                    index * (self.tiles_per_dimension + 1) + coordinate
+                                })
+                        }
+                    };
+                    (index, 1.0)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// # Radial Basis
+///
+/// Represents a state by its similarity to a set of centers.
+/// Each feature is the Gaussian `exp(-||s - c||^2 / (2 * width^2))`
+/// of the distance between the state and one of the centers,
+/// so that the features vary smoothly with the state.
+#[derive(Debug, Clone)]
+pub struct RadialBasis {
+    centers: Vec<Vec<f64>>,
+    width: f64,
+}
+
+impl RadialBasis {
+    /// Creates a new radial basis with the given centers and width.
+    pub fn new(centers: Vec<Vec<f64>>, width: f64) -> Self {
+        assert!(
+            !centers.is_empty(),
+            "radial basis must have at least one center."
+        );
+        assert!(width > 0.0, "width must be positive.");
+        Self { centers, width }
+    }
+}
+
+impl<S> StateFeatures<S> for RadialBasis
+where
+    S: ContinuousState,
+{
+    fn get_num_features(&self) -> usize {
+        self.centers.len()
+    }
+
+    fn get_features(&self, state: &S) -> FeatureVector {
+        let variables = state.get_variables();
+        FeatureVector::Dense(
+            self.centers
+                .iter()
+                .map(|center| {
+                    let squared_distance: f64 = center
+                        .iter()
+                        .zip(&variables)
+                        .map(|(c, x)| (x - c).powi(2))
+                        .sum();
+                    (-squared_distance / (2.0 * self.width.powi(2))).exp()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// # Fourier Basis
+///
+/// Represents a state by cosines of increasing frequencies.
+/// The variables are first scaled to `[0, 1]` with the given bounds,
+/// then each feature is `cos(pi * c^T s)` for an integer vector `c`
+/// whose entries lie between zero and the order.
+/// There are `(order + 1)^d` features for `d` variables.
+#[derive(Debug, Clone)]
+pub struct FourierBasis {
+    low: Vec<f64>,
+    high: Vec<f64>,
+    coefficients: Vec<Vec<u32>>,
+}
+
+impl FourierBasis {
+    /// Creates a new Fourier basis of the given order between the given bounds.
+    pub fn new(order: u32, low: Vec<f64>, high: Vec<f64>) -> Self {
+        check_bounds(&low, &high);
+        let coefficients = get_exponents(low.len(), order);
+        Self {
+            low,
+            high,
+            coefficients,
+        }
+    }
+}
+
+impl<S> StateFeatures<S> for FourierBasis
+where
+    S: ContinuousState,
+{
+    fn get_num_features(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    fn get_features(&self, state: &S) -> FeatureVector {
+        let scaled: Vec<f64> = state
+            .get_variables()
+            .iter()
+            .zip(self.low.iter().zip(&self.high))
+            .map(|(x, (low, high))| ((x - low) / (high - low)).clamp(0.0, 1.0))
+            .collect();
+        FeatureVector::Dense(
+            self.coefficients
+                .iter()
+                .map(|coefficient| {
+                    let frequency: f64 = coefficient
+                        .iter()
+                        .zip(&scaled)
+                        .map(|(c, x)| f64::from(*c) * x)
+                        .sum();
+                    (PI * frequency).cos()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// # Polynomial Basis
+///
+/// Represents a state by products of powers of its variables.
+/// Each feature is `x_1^c_1 * ... * x_d^c_d` for exponents between zero and the degree,
+/// including the constant feature for which all exponents are zero.
+/// There are `(degree + 1)^d` features for `d` variables.
+/// The variables should be scaled to similar ranges to keep the features well conditioned.
+#[derive(Debug, Clone)]
+pub struct PolynomialBasis {
+    exponents: Vec<Vec<u32>>,
+}
+
+impl PolynomialBasis {
+    /// Creates a new polynomial basis of the given degree for the given number of variables.
+    pub fn new(num_variables: usize, degree: u32) -> Self {
+        Self {
+            exponents: get_exponents(num_variables, degree),
+        }
+    }
+}
+
+impl<S> StateFeatures<S> for PolynomialBasis
+where
+    S: ContinuousState,
+{
+    fn get_num_features(&self) -> usize {
+        self.exponents.len()
+    }
+
+    fn get_features(&self, state: &S) -> FeatureVector {
+        let variables = state.get_variables();
+        FeatureVector::Dense(
+            self.exponents
+                .iter()
+                .map(|exponent| {
+                    exponent
+                        .iter()
+                        .zip(&variables)
+                        .map(|(c, x)| x.powi(*c as i32))
+                        .product()
+                })
+                .collect(),
+        )
+    }
+}
+
+/// # State Aggregation
+///
+/// Partitions the state space with a single grid
+/// and represents a state by the cell it belongs to,
+/// so that all states of a cell share the same value.
+/// Variables outside of the bounds are clamped to the border cells.
+#[derive(Debug, Clone)]
+pub struct StateAggregation {
+    groups_per_dimension: usize,
+    low: Vec<f64>,
+    group_widths: Vec<f64>,
+}
+
+impl StateAggregation {
+    /// Creates a new state aggregation with the given number of groups per dimension
+    /// between the given bounds.
+    pub fn new(groups_per_dimension: usize, low: Vec<f64>, high: Vec<f64>) -> Self {
+        assert!(
+            groups_per_dimension > 0,
+            "state aggregation must have at least one group per dimension."
+        );
+        check_bounds(&low, &high);
+        let group_widths = low
+            .iter()
+            .zip(&high)
+            .map(|(low, high)| (high - low) / groups_per_dimension as f64)
+            .collect();
+        Self {
+            groups_per_dimension,
+            low,
+            group_widths,
+        }
+    }
+}
+
+impl<S> StateFeatures<S> for StateAggregation
+where
+    S: ContinuousState,
+{
+    fn get_num_features(&self) -> usize {
+        self.groups_per_dimension.pow(self.low.len() as u32)
+    }
+
+    fn get_features(&self, state: &S) -> FeatureVector {
+        let index = state
+            .get_variables()
+            .iter()
+            .zip(self.low.iter().zip(&self.group_widths))
+            .rev()
+            .fold(0, |index, (variable, (low, width))| {
+                let group = get_interval(*variable, *low, *width, self.groups_per_dimension - 1);
+                index * self.groups_per_dimension + group
+            });
+        FeatureVector::Sparse(vec![(index, 1.0)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::models::{
+        Action, Config, Environment, Exploration, MDPEnvironment, PerActionFeatures, Sampler,
+        StateActionFeatures, StateActionValue, MDP,
+    };
+    use crate::solvers::approximation::{semi_gradient_q_learning, semi_gradient_td};
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct Point(Vec<u64>);
+    impl State for Point {}
+
+    impl ContinuousState for Point {
+        fn get_variables(&self) -> Vec<f64> {
+            self.0.iter().map(|bits| f64::from_bits(*bits)).collect()
+        }
+    }
+
+    fn point(variables: &[f64]) -> Point {
+        Point(variables.iter().map(|x| x.to_bits()).collect())
+    }
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    enum Move {
+        Left,
+        Right,
+    }
+    impl Action for Move {}
+
+    /// A corridor of ten cells between zero and one whose end is reached by moving right,
+    /// where each state lies at the center of its cell
+    /// and episodes start from the first cell
+    struct Corridor {
+        states: Sampler<Point>,
+        actions: Sampler<Move>,
+    }
+
+    fn get_cell_center(cell: i32) -> Point {
+        point(&[(f64::from(cell) + 0.5) / 10.0])
+    }
+
+    impl Corridor {
+        fn new() -> Self {
+            Self {
+                states: Sampler::new((0..10).map(get_cell_center).collect()),
+                actions: Sampler::new(vec![Move::Left, Move::Right]),
+            }
+        }
+    }
+
+    impl MDP for Corridor {
+        type State = Point;
+        type Action = Move;
+
+        fn get_states(&self) -> &Sampler<Self::State> {
+            &self.states
+        }
+
+        fn get_actions(&self) -> &Sampler<Self::Action> {
+            &self.actions
+        }
+
+        fn is_state_terminal(&self, state: &Self::State) -> bool {
+            state.get_variables()[0] >= 1.0
+        }
+
+        fn get_initial_state(&self) -> Self::State {
+            get_cell_center(0)
+        }
+
+        fn transition(&self, state: &Self::State, action: &Self::Action) -> (Self::State, f64) {
+            let cell = (state.get_variables()[0] * 10.0).floor() as i32;
+            let next_cell = match action {
+                Move::Left => (cell - 1).max(0),
+                Move::Right => cell + 1,
+            };
+            (get_cell_center(next_cell), -1.0)
+        }
+    }
+
+    /// Moves left on the first of every three visits of a state and right otherwise,
+    /// so that every action is tried in every cell without any randomness
+    #[derive(Default)]
+    struct Cyclic(HashMap<Point, u32>);
+
+    impl Exploration<Point, Move> for Cyclic {
+        fn choose(
+            &mut self,
+            state: &Point,
+            _state_action_value: &StateActionValue<Move>,
+            _actions: &Sampler<Move>,
+        ) -> Move {
+            let visits = self.0.entry(state.clone()).or_default();
+            *visits += 1;
+            if *visits % 3 == 1 {
+                Move::Left
+            } else {
+                Move::Right
+            }
+        }
+    }
+
+    #[test]
+    fn fnv1a_hash() {
+        // reference values of the 64-bit FNV-1a hash
+        assert_eq!(fnv1a(*b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(*b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(*b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn tile_coding_activates_one_tile_per_tiling() {
+        let tile_coding = TileCoding::new(4, 8, vec![0.0, 0.0], vec![1.0, 1.0]);
+        assert_eq!(
+            StateFeatures::<Point>::get_num_features(&tile_coding),
+            4 * 81
+        );
+        let features = tile_coding.get_features(&point(&[0.5, 0.25]));
+        let FeatureVector::Sparse(features) = features else {
+            panic!("tile coding features must be sparse.");
+        };
+        assert_eq!(features.len(), 4);
+        for (tiling, (index, feature)) in features.iter().enumerate() {
+            assert_eq!(index / 81, tiling);
+            assert_eq!(*feature, 1.0);
+        }
+    }
+
+    #[test]
+    fn tile_coding_generalizes_to_close_states() {
+        let tile_coding = TileCoding::new(8, 4, vec![0.0], vec![1.0]);
+        let active = |x: f64| match tile_coding.get_features(&point(&[x])) {
+            FeatureVector::Sparse(features) => features,
+            FeatureVector::Dense(_) => unreachable!(),
+        };
+        let shared = |x: f64, y: f64| active(x).iter().filter(|f| active(y).contains(f)).count();
+        assert_eq!(shared(0.5, 0.5), 8);
+        assert!(shared(0.5, 0.52) > shared(0.5, 0.6));
+        assert_eq!(shared(0.1, 0.9), 0);
+    }
+
+    #[test]
+    fn tile_coding_hashing() {
+        let tile_coding = TileCoding::new(8, 10, vec![0.0; 4], vec![1.0; 4]).hashing(512);
+        assert_eq!(StateFeatures::<Point>::get_num_features(&tile_coding), 512);
+        let features = tile_coding.get_features(&point(&[0.1, 0.2, 0.3, 0.4]));
+        assert_eq!(
+            features,
+            tile_coding.get_features(&point(&[0.1, 0.2, 0.3, 0.4]))
+        );
+        let FeatureVector::Sparse(features) = features else {
+            panic!("tile coding features must be sparse.");
+        };
+        assert!(features.iter().all(|(index, _)| *index < 512));
+        // the indices must not change, or the weights learned with them would be invalidated
+        let indices: Vec<usize> = features.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, vec![384, 416, 131, 98, 69, 36, 263, 230]);
+    }
+
+    #[test]
+    fn radial_basis() {
+        let radial_basis = RadialBasis::new(vec![vec![0.0], vec![1.0]], 0.5);
+        let features = radial_basis.get_features(&point(&[0.0]));
+        let FeatureVector::Dense(features) = features else {
+            panic!("radial basis features must be dense.");
+        };
+        assert_eq!(features[0], 1.0);
+        assert!((features[1] - (-2.0_f64).exp()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fourier_basis() {
+        let fourier_basis = FourierBasis::new(2, vec![0.0, 0.0], vec![2.0, 2.0]);
+        assert_eq!(StateFeatures::<Point>::get_num_features(&fourier_basis), 9);
+        let features = fourier_basis.get_features(&point(&[1.0, 0.0])).to_dense(9);
+        // the first feature is constant and the second one is cos(pi * s_2)
+        assert_eq!(features[0], 1.0);
+        assert_eq!(features[1], 1.0);
+        // the feature with coefficients (1, 0) is cos(pi / 2)
+        assert!(features[3].abs() < 1e-12);
+    }
+
+    #[test]
+    fn polynomial_basis() {
+        let polynomial_basis = PolynomialBasis::new(2, 1);
+        let features = polynomial_basis.get_features(&point(&[2.0, 3.0]));
+        assert_eq!(features, FeatureVector::Dense(vec![1.0, 3.0, 2.0, 6.0]));
+    }
+
+    #[test]
+    fn state_aggregation() {
+        let state_aggregation = StateAggregation::new(4, vec![0.0, 0.0], vec![1.0, 1.0]);
+        assert_eq!(
+            StateFeatures::<Point>::get_num_features(&state_aggregation),
+            16
+        );
+        assert_eq!(
+            state_aggregation.get_features(&point(&[0.3, 0.8])),
+            FeatureVector::Sparse(vec![(13, 1.0)])
+        );
+        assert_eq!(
+            state_aggregation.get_features(&point(&[1.5, -1.0])),
+            FeatureVector::Sparse(vec![(3, 1.0)])
+        );
+    }
+
+    #[test]
+    fn semi_gradient_td_with_state_aggregation() {
+        let mdp = Corridor::new();
+        let config = Config::new()
+            .discount_factor(1.0)
+            .learning_rate(0.05)
+            .num_episodes(2_000);
+        let mut env = MDPEnvironment::new(&mdp);
+        let state_aggregation = StateAggregation::new(10, vec![0.0], vec![1.0]);
+        let state_value = semi_gradient_td(&mut env, &config, state_aggregation, |_| Move::Right);
+        // the state of a cell is as many steps away from the end as there are cells left
+        for (cell, expected) in [(0, -10.0), (5, -5.0), (9, -1.0)] {
+            assert!((state_value.get(&get_cell_center(cell)) - expected).abs() < 0.5);
+        }
+    }
+
+    #[test]
+    fn semi_gradient_q_learning_with_tile_coding() {
+        let mdp = Corridor::new();
+        let config = Config::new()
+            .discount_factor(1.0)
+            .learning_rate(0.1 / 4.0)
+            .num_episodes(500)
+            .max_num_steps(100);
+        let mut exploration = Cyclic::default();
+        let mut env = MDPEnvironment::new(&mdp);
+        let features = PerActionFeatures::new(
+            TileCoding::new(4, 10, vec![0.0], vec![1.0]),
+            env.get_actions(),
+        );
+        assert_eq!(
+            StateActionFeatures::<Point, Move>::get_num_features(&features),
+            2 * 4 * 11
+        );
+        let action_value = semi_gradient_q_learning(&mut env, &config, &mut exploration, features);
+        for state in mdp.get_states() {
+            assert_eq!(action_value.greedy(state, mdp.get_actions()), &Move::Right);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::defaults::{
-    DISCOUNT_FACTOR, EXPLORATION_RATE, ITERATIONS_BEFORE_IMPROVEMENT, LEARNING_RATE, MAX_NUM_STEPS,
-    NUM_EPISODES,
+    BATCH_SIZE, DISCOUNT_FACTOR, EXPLORATION_CONSTANT, EXPLORATION_RATE,
+    ITERATIONS_BEFORE_IMPROVEMENT, LEARNING_RATE, MAX_NUM_ITERATIONS, MAX_NUM_STEPS, NUM_EPISODES,
+    NUM_SIMULATIONS, REPLAY_RATIO, SECONDARY_LEARNING_RATE, TEMPERATURE, TOLERANCE, TRACE_DECAY,
 };
 
 /// # Config
@@ -15,6 +16,15 @@ pub struct Config {
     pub learning_rate: f64,
     pub exploration_rate: f64,
     pub iterations_before_improvement: Option<u32>,
+    pub tolerance: f64,
+    pub max_num_iterations: u32,
+    pub batch_size: usize,
+    pub replay_ratio: u32,
+    pub secondary_learning_rate: f64,
+    pub trace_decay: f64,
+    pub temperature: f64,
+    pub num_simulations: u32,
+    pub exploration_constant: f64,
 }
 
 impl Config {
@@ -27,6 +37,15 @@ impl Config {
             learning_rate: LEARNING_RATE,
             exploration_rate: EXPLORATION_RATE,
             iterations_before_improvement: ITERATIONS_BEFORE_IMPROVEMENT,
+            tolerance: TOLERANCE,
+            max_num_iterations: MAX_NUM_ITERATIONS,
+            batch_size: BATCH_SIZE,
+            replay_ratio: REPLAY_RATIO,
+            secondary_learning_rate: SECONDARY_LEARNING_RATE,
+            trace_decay: TRACE_DECAY,
+            temperature: TEMPERATURE,
+            num_simulations: NUM_SIMULATIONS,
+            exploration_constant: EXPLORATION_CONSTANT,
         }
     }
 
@@ -68,6 +87,60 @@ impl Config {
         self.iterations_before_improvement = iterations_before_improvement;
         self
     }
+
+    /// Sets the convergence tolerance and returns the config.
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum number of iterations and returns the config.
+    pub fn max_num_iterations(mut self, max_num_iterations: u32) -> Self {
+        self.max_num_iterations = max_num_iterations;
+        self
+    }
+
+    /// Sets the number of transitions per replayed minibatch and returns the config.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the number of replayed minibatches per environment step and returns the config.
+    pub fn replay_ratio(mut self, replay_ratio: u32) -> Self {
+        self.replay_ratio = replay_ratio;
+        self
+    }
+
+    /// Sets the learning rate of the secondary weights and returns the config.
+    pub fn secondary_learning_rate(mut self, secondary_learning_rate: f64) -> Self {
+        self.secondary_learning_rate = secondary_learning_rate;
+        self
+    }
+
+    /// Sets the decay rate of eligibility traces and returns the config.
+    pub fn trace_decay(mut self, trace_decay: f64) -> Self {
+        self.trace_decay = trace_decay;
+        self
+    }
+
+    /// Sets the temperature of entropy-regularized algorithms and returns the config.
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    /// Sets the number of simulations of tree search planners and returns the config.
+    pub fn num_simulations(mut self, num_simulations: u32) -> Self {
+        self.num_simulations = num_simulations;
+        self
+    }
+
+    /// Sets the exploration constant of tree search planners and returns the config.
+    pub fn exploration_constant(mut self, exploration_constant: f64) -> Self {
+        self.exploration_constant = exploration_constant;
+        self
+    }
 }
 
 impl Default for Config {
@@ -0,0 +1,130 @@
+use super::{Action, Sampler, State, MDP};
+
+/// # Environment
+///
+/// Represents an interactive environment that can only be played forward.
+/// An episode starts with [`Environment::reset`]
+/// and then proceeds with calls to [`Environment::step`].
+/// You have to implement this trait for your own simulator,
+/// or you can wrap any [`MDP`] in an [`MDPEnvironment`].
+pub trait Environment {
+    type State: State;
+    type Action: Action;
+
+    /// Returns a reference to the action sampler.
+    fn get_actions(&self) -> &Sampler<Self::Action>;
+
+    /// Starts a new episode and returns its initial state.
+    fn reset(&mut self) -> Self::State;
+
+    /// Given an action, advances the environment and returns
+    /// the next state, the reward, whether the episode is done
+    /// and whether the episode was truncated.
+    fn step(&mut self, action: &Self::Action) -> (Self::State, f64, bool, bool);
+}
+
+/// # MDP Environment
+///
+/// Adapts an [`MDP`] to the [`Environment`] trait.
+/// Episodes start from [`MDP::get_initial_state`]
+/// and are done when a terminal state is reached.
+/// If a maximum number of steps is set,
+/// episodes are truncated once it is reached.
+pub struct MDPEnvironment<'a, M>
+where
+    M: MDP,
+{
+    mdp: &'a M,
+    state: Option<M::State>,
+    num_steps: u32,
+    max_num_steps: Option<u32>,
+}
+
+impl<'a, M> MDPEnvironment<'a, M>
+where
+    M: MDP,
+{
+    /// Creates a new environment from the given MDP.
+    pub fn new(mdp: &'a M) -> Self {
+        Self {
+            mdp,
+            state: None,
+            num_steps: 0,
+            max_num_steps: None,
+        }
+    }
+
+    /// Sets the maximum number of steps per episode and returns the environment.
+    pub fn max_num_steps(mut self, max_num_steps: u32) -> Self {
+        self.max_num_steps = Some(max_num_steps);
+        self
+    }
+}
+
+impl<M> Environment for MDPEnvironment<'_, M>
+where
+    M: MDP,
+{
+    type State = M::State;
+    type Action = M::Action;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        self.mdp.get_actions()
+    }
+
+    fn reset(&mut self) -> Self::State {
+        let state = self.mdp.get_initial_state();
+        self.state = Some(state.clone());
+        self.num_steps = 0;
+        state
+    }
+
+    fn step(&mut self, action: &Self::Action) -> (Self::State, f64, bool, bool) {
+        let state = self
+            .state
+            .as_ref()
+            .expect("environment must be reset before stepping.");
+        let (next_state, reward) = self.mdp.transition(state, action);
+        self.num_steps += 1;
+        let done = self.mdp.is_state_terminal(&next_state);
+        let truncated = !done && self.max_num_steps.is_some_and(|n| self.num_steps >= n);
+        self.state = Some(next_state.clone());
+        (next_state, reward, done, truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{get_gridworld, GridworldAction, GridworldState};
+
+    #[test]
+    fn step_to_terminal() {
+        let mdp = get_gridworld().initial_states(vec![GridworldState::new(0, 1)]);
+        let mut env = MDPEnvironment::new(&mdp);
+        assert_eq!(env.reset(), GridworldState::new(0, 1));
+        let (state, _, done, truncated) = env.step(&GridworldAction::Down);
+        assert_eq!(state, GridworldState::new(1, 1));
+        assert!(done);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn truncated_after_max_num_steps() {
+        let mdp = get_gridworld().initial_states(vec![GridworldState::new(0, 0)]);
+        let mut env = MDPEnvironment::new(&mdp).max_num_steps(2);
+        env.reset();
+        let (_, _, done, truncated) = env.step(&GridworldAction::Up);
+        assert!(!done && !truncated);
+        let (_, _, done, truncated) = env.step(&GridworldAction::Up);
+        assert!(!done && truncated);
+    }
+
+    #[test]
+    #[should_panic(expected = "reset")]
+    fn step_before_reset() {
+        let mdp = get_gridworld();
+        let mut env = MDPEnvironment::new(&mdp);
+        env.step(&GridworldAction::Up);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use super::{Action, Sampler, State, StateActionValue};
+
+/// # Exploration
+///
+/// Represents a strategy to select actions from action values
+/// while balancing exploration and exploitation.
+/// You can implement this trait for your own exploration strategy.
+pub trait Exploration<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Returns the value the action values should be initialized with.
+    fn initial_value(&self) -> f64 {
+        0.0
+    }
+
+    /// Given a state and its action values, returns the action to take.
+    fn choose(
+        &mut self,
+        state: &S,
+        state_action_value: &StateActionValue<A>,
+        actions: &Sampler<A>,
+    ) -> A;
+}
+
+/// # Epsilon Greedy
+///
+/// Selects a random action with probability epsilon
+/// and the greedy action with probability 1 - epsilon.
+#[derive(Debug, Clone)]
+pub struct EpsilonGreedy {
+    epsilon: f64,
+}
+
+impl EpsilonGreedy {
+    /// Creates a new epsilon-greedy strategy with the given exploration rate.
+    pub fn new(epsilon: f64) -> Self {
+        Self { epsilon }
+    }
+}
+
+impl<S, A> Exploration<S, A> for EpsilonGreedy
+where
+    S: State,
+    A: Action,
+{
+    fn choose(
+        &mut self,
+        _state: &S,
+        state_action_value: &StateActionValue<A>,
+        actions: &Sampler<A>,
+    ) -> A {
+        state_action_value
+            .epsilon_greedy(actions, self.epsilon)
+            .clone()
+    }
+}
+
+/// # Boltzmann
+///
+/// Samples actions from the softmax of their values with the given temperature.
+/// High temperatures lead to uniform exploration
+/// while low temperatures lead to greedy exploitation.
+#[derive(Debug, Clone)]
+pub struct Boltzmann {
+    temperature: f64,
+}
+
+impl Boltzmann {
+    /// Creates a new Boltzmann strategy with the given temperature.
+    pub fn new(temperature: f64) -> Self {
+        assert!(temperature > 0.0, "temperature must be positive.");
+        Self { temperature }
+    }
+}
+
+impl<S, A> Exploration<S, A> for Boltzmann
+where
+    S: State,
+    A: Action,
+{
+    fn choose(
+        &mut self,
+        _state: &S,
+        state_action_value: &StateActionValue<A>,
+        _actions: &Sampler<A>,
+    ) -> A {
+        state_action_value.softmax(self.temperature).clone()
+    }
+}
+
+/// # Upper Confidence Bound
+///
+/// Selects the action maximizing its value plus a count-based bonus
+/// `c * sqrt(ln(N(s)) / N(s, a))`.
+/// Actions that have never been selected in a state are tried first.
+/// The counts are recorded each time an action is chosen.
+#[derive(Debug, Clone)]
+pub struct UCB<S, A>
+where
+    S: State,
+    A: Action,
+{
+    c: f64,
+    counts: HashMap<S, HashMap<A, u32>>,
+}
+
+impl<S, A> UCB<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a new UCB strategy with the given exploration constant.
+    pub fn new(c: f64) -> Self {
+        Self {
+            c,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of times the given action was chosen in the given state.
+    pub fn get_count(&self, state: &S, action: &A) -> u32 {
+        self.counts
+            .get(state)
+            .and_then(|counts| counts.get(action))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl<S, A> Exploration<S, A> for UCB<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn choose(
+        &mut self,
+        state: &S,
+        state_action_value: &StateActionValue<A>,
+        actions: &Sampler<A>,
+    ) -> A {
+        let counts = self.counts.entry(state.clone()).or_default();
+        let total: u32 = counts.values().sum();
+        let mut best_action = None;
+        let mut best_value = f64::NEG_INFINITY;
+        for action in actions {
+            let count = counts.get(action).copied().unwrap_or(0);
+            if count == 0 {
+                best_action = Some(action);
+                break;
+            }
+            let bonus = self.c * ((total as f64).ln() / count as f64).sqrt();
+            let value = state_action_value.get(action) + bonus;
+            if value > best_value {
+                best_value = value;
+                best_action = Some(action);
+            }
+        }
+        // unwrap is safe because actions is not empty
+        let action = best_action.unwrap().clone();
+        *counts.entry(action.clone()).or_insert(0) += 1;
+        action
+    }
+}
+
+/// # Optimistic Initial Values
+///
+/// Always selects the greedy action but initializes
+/// the action values optimistically so that untried actions look promising.
+#[derive(Debug, Clone)]
+pub struct OptimisticInitialValues {
+    initial_value: f64,
+}
+
+impl OptimisticInitialValues {
+    /// Creates a new optimistic strategy with the given initial value.
+    pub fn new(initial_value: f64) -> Self {
+        Self { initial_value }
+    }
+}
+
+impl<S, A> Exploration<S, A> for OptimisticInitialValues
+where
+    S: State,
+    A: Action,
+{
+    fn initial_value(&self) -> f64 {
+        self.initial_value
+    }
+
+    fn choose(
+        &mut self,
+        _state: &S,
+        state_action_value: &StateActionValue<A>,
+        _actions: &Sampler<A>,
+    ) -> A {
+        state_action_value.greedy().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState;
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction(usize);
+    impl Action for TestAction {}
+
+    fn get_actions() -> Sampler<TestAction> {
+        Sampler::new(vec![TestAction(0), TestAction(1)])
+    }
+
+    fn get_state_action_value() -> StateActionValue<TestAction> {
+        let mut state_action_value = StateActionValue::new(&get_actions());
+        state_action_value.insert(&TestAction(1), 1.0);
+        state_action_value
+    }
+
+    #[test]
+    fn epsilon_greedy_without_exploration() {
+        let mut exploration = EpsilonGreedy::new(0.0);
+        let action = exploration.choose(&TestState, &get_state_action_value(), &get_actions());
+        assert_eq!(action, TestAction(1));
+    }
+
+    #[test]
+    fn boltzmann_with_low_temperature() {
+        let mut exploration = Boltzmann::new(1e-3);
+        let action = exploration.choose(&TestState, &get_state_action_value(), &get_actions());
+        assert_eq!(action, TestAction(1));
+    }
+
+    #[test]
+    fn ucb_tries_every_action_first() {
+        let mut exploration = UCB::new(1.0);
+        let state_action_value = get_state_action_value();
+        let first = exploration.choose(&TestState, &state_action_value, &get_actions());
+        let second = exploration.choose(&TestState, &state_action_value, &get_actions());
+        assert_ne!(first, second);
+        assert_eq!(exploration.get_count(&TestState, &TestAction(0)), 1);
+        assert_eq!(exploration.get_count(&TestState, &TestAction(1)), 1);
+    }
+
+    #[test]
+    fn optimistic_initial_values() {
+        let mut exploration = OptimisticInitialValues::new(10.0);
+        assert_eq!(
+            Exploration::<TestState, TestAction>::initial_value(&exploration),
+            10.0
+        );
+        let action = exploration.choose(&TestState, &get_state_action_value(), &get_actions());
+        assert_eq!(action, TestAction(1));
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use crate::errors::NotFound;
+
+use super::{Action, Sampler, State};
+
+/// Returns the dot product of two vectors.
+pub fn dot(left: &[f64], right: &[f64]) -> f64 {
+    left.iter().zip(right).map(|(l, r)| l * r).sum()
+}
+
+/// # Feature Vector
+///
+/// Represents the features of a state or of a state-action pair.
+/// Dense vectors store every feature,
+/// while sparse vectors only store the non-zero features along with their indices.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeatureVector {
+    Dense(Vec<f64>),
+    Sparse(Vec<(usize, f64)>),
+}
+
+impl FeatureVector {
+    /// Returns the dot product of the features with the given weights.
+    pub fn dot(&self, weights: &[f64]) -> f64 {
+        match self {
+            FeatureVector::Dense(features) => dot(features, weights),
+            FeatureVector::Sparse(features) => features.iter().map(|(i, x)| x * weights[*i]).sum(),
+        }
+    }
+
+    /// Adds the features multiplied by the given scale to the given weights.
+    pub fn add_scaled_to(&self, weights: &mut [f64], scale: f64) {
+        match self {
+            FeatureVector::Dense(features) => {
+                for (w, x) in weights.iter_mut().zip(features) {
+                    *w += scale * x;
+                }
+            }
+            FeatureVector::Sparse(features) => {
+                for (i, x) in features {
+                    weights[*i] += scale * x;
+                }
+            }
+        }
+    }
+
+    /// Returns the features as a dense vector of the given length.
+    pub fn to_dense(&self, num_features: usize) -> Vec<f64> {
+        let mut dense = vec![0.0; num_features];
+        self.add_scaled_to(&mut dense, 1.0);
+        dense
+    }
+}
+
+/// # State Features
+///
+/// Represents a mapping from states to feature vectors.
+/// You can implement this trait for your own feature extractor.
+pub trait StateFeatures<S>
+where
+    S: State,
+{
+    /// Returns the number of features.
+    fn get_num_features(&self) -> usize;
+
+    /// Returns the features of the given state.
+    fn get_features(&self, state: &S) -> FeatureVector;
+}
+
+/// # State Action Features
+///
+/// Represents a mapping from state-action pairs to feature vectors.
+/// You can implement this trait for your own feature extractor
+/// or build one from state features with [`PerActionFeatures`].
+pub trait StateActionFeatures<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Returns the number of features.
+    fn get_num_features(&self) -> usize;
+
+    /// Returns the features of the given state-action pair.
+    fn get_features(&self, state: &S, action: &A) -> FeatureVector;
+}
+
+/// # One Hot
+///
+/// Maps each state of a finite set to its own feature.
+/// Linear approximation with these features is equivalent to a table,
+/// which makes them useful to check approximate solvers against tabular ones.
+#[derive(Debug, Clone)]
+pub struct OneHot<S>(HashMap<S, usize>)
+where
+    S: State;
+
+impl<S> OneHot<S>
+where
+    S: State,
+{
+    /// Creates new one-hot features for the given states.
+    pub fn new(states: &Sampler<S>) -> Self {
+        let mut map = HashMap::new();
+        for state in states {
+            let index = map.len();
+            map.entry(state.clone()).or_insert(index);
+        }
+        Self(map)
+    }
+}
+
+impl<S> StateFeatures<S> for OneHot<S>
+where
+    S: State,
+{
+    fn get_num_features(&self) -> usize {
+        self.0.len()
+    }
+
+    fn get_features(&self, state: &S) -> FeatureVector {
+        let index = *self
+            .0
+            .get(state)
+            .unwrap_or_else(|| panic!("{}", NotFound::StateInFeatures));
+        FeatureVector::Sparse(vec![(index, 1.0)])
+    }
+}
+
+/// # Per Action Features
+///
+/// Builds state-action features from state features
+/// by placing the state features in a block dedicated to the action.
+/// Each action thus has its own set of weights.
+#[derive(Debug, Clone)]
+pub struct PerActionFeatures<F, A>
+where
+    A: Action,
+{
+    state_features: F,
+    blocks: HashMap<A, usize>,
+}
+
+impl<F, A> PerActionFeatures<F, A>
+where
+    A: Action,
+{
+    /// Creates new per-action features from the given state features and actions.
+    pub fn new(state_features: F, actions: &Sampler<A>) -> Self {
+        let mut blocks = HashMap::new();
+        for action in actions {
+            let block = blocks.len();
+            blocks.entry(action.clone()).or_insert(block);
+        }
+        Self {
+            state_features,
+            blocks,
+        }
+    }
+
+    /// Returns a reference to the underlying state features.
+    pub fn get_state_features(&self) -> &F {
+        &self.state_features
+    }
+}
+
+impl<S, A, F> StateActionFeatures<S, A> for PerActionFeatures<F, A>
+where
+    S: State,
+    A: Action,
+    F: StateFeatures<S>,
+{
+    fn get_num_features(&self) -> usize {
+        self.blocks.len() * self.state_features.get_num_features()
+    }
+
+    fn get_features(&self, state: &S, action: &A) -> FeatureVector {
+        let block = *self
+            .blocks
+            .get(action)
+            .unwrap_or_else(|| panic!("{}", NotFound::ActionInFeatures));
+        let offset = block * self.state_features.get_num_features();
+        let features = match self.state_features.get_features(state) {
+            FeatureVector::Dense(features) => features.into_iter().enumerate().collect(),
+            FeatureVector::Sparse(features) => features,
+        };
+        FeatureVector::Sparse(
+            features
+                .into_iter()
+                .map(|(index, feature)| (offset + index, feature))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState(usize);
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction(usize);
+    impl Action for TestAction {}
+
+    fn get_states() -> Sampler<TestState> {
+        Sampler::new(vec![TestState(0), TestState(1), TestState(2)])
+    }
+
+    fn get_actions() -> Sampler<TestAction> {
+        Sampler::new(vec![TestAction(0), TestAction(1)])
+    }
+
+    #[test]
+    fn dense_and_sparse_agree() {
+        let dense = FeatureVector::Dense(vec![0.0, 2.0, 0.0, -1.0]);
+        let sparse = FeatureVector::Sparse(vec![(1, 2.0), (3, -1.0)]);
+        let weights = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(dense.dot(&weights), 0.0);
+        assert_eq!(sparse.dot(&weights), 0.0);
+        assert_eq!(dot(&[0.0, 2.0, 0.0, -1.0], &weights), 0.0);
+        assert_eq!(sparse.to_dense(4), vec![0.0, 2.0, 0.0, -1.0]);
+        let mut weights = vec![0.0; 4];
+        dense.add_scaled_to(&mut weights, 0.5);
+        sparse.add_scaled_to(&mut weights, 0.5);
+        assert_eq!(weights, vec![0.0, 2.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn one_hot() {
+        let features = OneHot::new(&get_states());
+        assert_eq!(features.get_num_features(), 3);
+        let indices: Vec<usize> = get_states()
+            .iter()
+            .map(|state| match features.get_features(state) {
+                FeatureVector::Sparse(features) => features[0].0,
+                FeatureVector::Dense(_) => unreachable!(),
+            })
+            .collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn per_action_features() {
+        let features = PerActionFeatures::new(OneHot::new(&get_states()), &get_actions());
+        assert_eq!(
+            StateActionFeatures::<TestState, TestAction>::get_num_features(&features),
+            6
+        );
+        assert_eq!(
+            features.get_features(&TestState(2), &TestAction(1)),
+            FeatureVector::Sparse(vec![(5, 1.0)])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "state")]
+    fn unknown_state_in_one_hot() {
+        let features = OneHot::new(&get_states());
+        features.get_features(&TestState(3));
+    }
+}
@@ -0,0 +1,337 @@
+use std::marker::PhantomData;
+
+use super::{
+    Action, Policy, Sampler, State, StateActionFeatures, StateActionValue, StateFeatures,
+    StateValue, MDP,
+};
+
+/// # Linear State Value
+///
+/// Represents an approximate mapping from states to values.
+/// The value of a state is the dot product of its features with a weight vector,
+/// so that the memory used does not depend on the number of states.
+#[derive(Debug, Clone)]
+pub struct LinearStateValue<S, F>
+where
+    S: State,
+    F: StateFeatures<S>,
+{
+    features: F,
+    weights: Vec<f64>,
+    state: PhantomData<S>,
+}
+
+impl<S, F> LinearStateValue<S, F>
+where
+    S: State,
+    F: StateFeatures<S>,
+{
+    /// Creates a new linear state value with all weights set to zero.
+    pub fn new(features: F) -> Self {
+        let weights = vec![0.0; features.get_num_features()];
+        Self::with_weights(features, weights)
+    }
+
+    /// Creates a new linear state value with the given weights.
+    pub fn with_weights(features: F, weights: Vec<f64>) -> Self {
+        assert_eq!(
+            weights.len(),
+            features.get_num_features(),
+            "there must be one weight per feature."
+        );
+        Self {
+            features,
+            weights,
+            state: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the feature extractor.
+    pub fn get_features(&self) -> &F {
+        &self.features
+    }
+
+    /// Returns the weights.
+    pub fn get_weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns a mutable reference to the weights.
+    pub fn get_weights_mut(&mut self) -> &mut [f64] {
+        &mut self.weights
+    }
+
+    /// Returns the approximate value of the given state.
+    pub fn get(&self, state: &S) -> f64 {
+        self.features.get_features(state).dot(&self.weights)
+    }
+
+    /// Moves the weights by the given step in the direction of the gradient
+    /// of the value of the given state, which is its feature vector.
+    pub fn update(&mut self, state: &S, step: f64) {
+        self.features
+            .get_features(state)
+            .add_scaled_to(&mut self.weights, step);
+    }
+
+    /// Returns the tabular state value of the given states.
+    pub fn to_state_value(&self, states: &Sampler<S>) -> StateValue<S> {
+        let mut state_value = StateValue::new(states);
+        for state in states {
+            state_value.insert(state, self.get(state));
+        }
+        state_value
+    }
+}
+
+/// # Linear Action Value
+///
+/// Represents an approximate mapping from state-action pairs to values.
+/// The value of a state-action pair is the dot product of its features with a weight vector.
+#[derive(Debug, Clone)]
+pub struct LinearActionValue<S, A, F>
+where
+    S: State,
+    A: Action,
+    F: StateActionFeatures<S, A>,
+{
+    features: F,
+    weights: Vec<f64>,
+    state_action: PhantomData<(S, A)>,
+}
+
+impl<S, A, F> LinearActionValue<S, A, F>
+where
+    S: State,
+    A: Action,
+    F: StateActionFeatures<S, A>,
+{
+    /// Creates a new linear action value with all weights set to zero.
+    pub fn new(features: F) -> Self {
+        let weights = vec![0.0; features.get_num_features()];
+        Self::with_weights(features, weights)
+    }
+
+    /// Creates a new linear action value with the given weights.
+    pub fn with_weights(features: F, weights: Vec<f64>) -> Self {
+        assert_eq!(
+            weights.len(),
+            features.get_num_features(),
+            "there must be one weight per feature."
+        );
+        Self {
+            features,
+            weights,
+            state_action: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the feature extractor.
+    pub fn get_features(&self) -> &F {
+        &self.features
+    }
+
+    /// Returns the weights.
+    pub fn get_weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns a mutable reference to the weights.
+    pub fn get_weights_mut(&mut self) -> &mut [f64] {
+        &mut self.weights
+    }
+
+    /// Returns the approximate value of the given state-action pair.
+    pub fn get(&self, state: &S, action: &A) -> f64 {
+        self.features.get_features(state, action).dot(&self.weights)
+    }
+
+    /// Moves the weights by the given step in the direction of the gradient
+    /// of the value of the given state-action pair, which is its feature vector.
+    pub fn update(&mut self, state: &S, action: &A, step: f64) {
+        self.features
+            .get_features(state, action)
+            .add_scaled_to(&mut self.weights, step);
+    }
+
+    /// Returns the approximate values of the given actions in the given state.
+    pub fn get_state_action_value(&self, state: &S, actions: &Sampler<A>) -> StateActionValue<A> {
+        let mut state_action_value = StateActionValue::new(actions);
+        for action in actions {
+            state_action_value.insert(action, self.get(state, action));
+        }
+        state_action_value
+    }
+
+    /// Returns the action with the highest value for the given state.
+    pub fn greedy<'a>(&self, state: &S, actions: &'a Sampler<A>) -> &'a A {
+        let (best_action, _) = actions
+            .iter()
+            .map(|action| (action, self.get(state, action)))
+            .reduce(|(best_action, best_value), (action, value)| {
+                if value > best_value {
+                    (action, value)
+                } else {
+                    (best_action, best_value)
+                }
+            })
+            // unwrap is safe because the sampler is not empty
+            .unwrap();
+        best_action
+    }
+
+    /// Returns a policy that maps each of the given states to the action with the highest value.
+    pub fn greedy_policy(&self, states: &Sampler<S>, actions: &Sampler<A>) -> Policy<S, A> {
+        let mut policy = Policy::new(states, actions);
+        for state in states {
+            policy.insert(state, self.greedy(state, actions));
+        }
+        policy
+    }
+}
+
+/// # Linear Reward MDP
+///
+/// Wraps an MDP and replaces its rewards with a linear reward in the given state features.
+/// The reward of a transition is the dot product of the weights
+/// with the features of the state it leads to.
+/// The states, actions, transitions and terminal states are those of the wrapped MDP.
+/// It is used by the inverse reinforcement learning solvers
+/// to plan with the rewards they learn.
+pub struct LinearRewardMDP<'a, M, F> {
+    mdp: &'a M,
+    features: &'a F,
+    weights: &'a [f64],
+}
+
+impl<'a, M, F> LinearRewardMDP<'a, M, F>
+where
+    M: MDP,
+    F: StateFeatures<M::State>,
+{
+    /// Creates a new linear reward MDP from the given MDP, features and weights.
+    pub fn new(mdp: &'a M, features: &'a F, weights: &'a [f64]) -> Self {
+        assert_eq!(
+            weights.len(),
+            features.get_num_features(),
+            "there must be one weight per feature."
+        );
+        Self {
+            mdp,
+            features,
+            weights,
+        }
+    }
+
+    /// Returns the reward weights.
+    pub fn get_weights(&self) -> &[f64] {
+        self.weights
+    }
+}
+
+impl<M, F> MDP for LinearRewardMDP<'_, M, F>
+where
+    M: MDP,
+    F: StateFeatures<M::State>,
+{
+    type State = M::State;
+    type Action = M::Action;
+
+    fn get_states(&self) -> &Sampler<Self::State> {
+        self.mdp.get_states()
+    }
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        self.mdp.get_actions()
+    }
+
+    fn is_state_terminal(&self, state: &Self::State) -> bool {
+        self.mdp.is_state_terminal(state)
+    }
+
+    fn transition(&self, state: &Self::State, action: &Self::Action) -> (Self::State, f64) {
+        let (next_state, _) = self.mdp.transition(state, action);
+        let reward = self.features.get_features(&next_state).dot(self.weights);
+        (next_state, reward)
+    }
+
+    fn get_initial_state(&self) -> Self::State {
+        self.mdp.get_initial_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{get_gridworld, GridworldAction, GridworldState};
+    use crate::models::{FeatureVector, OneHot, PerActionFeatures};
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState(usize);
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction(usize);
+    impl Action for TestAction {}
+
+    /// Represents a state by its index and a constant bias
+    struct TestFeatures;
+
+    impl StateFeatures<TestState> for TestFeatures {
+        fn get_num_features(&self) -> usize {
+            2
+        }
+
+        fn get_features(&self, state: &TestState) -> FeatureVector {
+            FeatureVector::Dense(vec![state.0 as f64, 1.0])
+        }
+    }
+
+    fn get_states() -> Sampler<TestState> {
+        Sampler::new(vec![TestState(0), TestState(1)])
+    }
+
+    fn get_actions() -> Sampler<TestAction> {
+        Sampler::new(vec![TestAction(0), TestAction(1)])
+    }
+
+    #[test]
+    fn linear_state_value() {
+        let mut state_value = LinearStateValue::new(TestFeatures);
+        state_value.update(&TestState(1), 1.0);
+        assert_eq!(state_value.get_weights(), &[1.0, 1.0]);
+        assert_eq!(state_value.get(&TestState(0)), 1.0);
+        assert_eq!(state_value.get(&TestState(3)), 4.0);
+        let table = state_value.to_state_value(&get_states());
+        assert_eq!(table.get(&TestState(1)), 2.0);
+    }
+
+    #[test]
+    fn linear_action_value_greedy_policy() {
+        let features = PerActionFeatures::new(OneHot::new(&get_states()), &get_actions());
+        let mut action_value = LinearActionValue::new(features);
+        action_value.update(&TestState(0), &TestAction(1), 1.0);
+        action_value.update(&TestState(1), &TestAction(0), 2.0);
+        assert_eq!(action_value.get(&TestState(1), &TestAction(0)), 2.0);
+        assert_eq!(action_value.get(&TestState(1), &TestAction(1)), 0.0);
+        let policy = action_value.greedy_policy(&get_states(), &get_actions());
+        assert_eq!(policy.get(&TestState(0)), &TestAction(1));
+        assert_eq!(policy.get(&TestState(1)), &TestAction(0));
+    }
+
+    #[test]
+    fn linear_reward_mdp() {
+        let mdp = get_gridworld();
+        let features = OneHot::new(mdp.get_states());
+        let weights: Vec<f64> = (0..features.get_num_features())
+            .map(|index| index as f64)
+            .collect();
+        let reward_mdp = LinearRewardMDP::new(&mdp, &features, &weights);
+        let top_left = GridworldState::new(0, 0);
+        let top_right = GridworldState::new(0, 1);
+        let (next_state, reward) = reward_mdp.transition(&top_left, &GridworldAction::Right);
+        assert_eq!(next_state, top_right);
+        assert_eq!(reward, features.get_features(&top_right).dot(&weights));
+        assert!(reward_mdp.is_state_terminal(&GridworldState::new(1, 1)));
+    }
+}
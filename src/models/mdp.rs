@@ -1,5 +1,7 @@
 use std::hash::Hash;
 
+use rand::prelude::*;
+
 use super::Sampler;
 
 /// # State
@@ -48,6 +50,8 @@ pub trait Action: Eq + Hash + Clone {}
 /// You should allocate the state and action samplers
 /// in the constructor of your MDP.
 /// You can use the [`Sampler`] struct for this purpose.
+/// Episodic solvers start their episodes from [`MDP::get_initial_state`],
+/// which you can override to provide your own initial state distribution.
 pub trait MDP {
     type State: State;
     type Action: Action;
@@ -63,4 +67,65 @@ pub trait MDP {
 
     /// Given a state and an action, returns the next state and reward.
     fn transition(&self, state: &Self::State, action: &Self::Action) -> (Self::State, f64);
+
+    /// Returns a state from which an episode starts.
+    /// By default, a non-terminal state is sampled uniformly at random.
+    fn get_initial_state(&self) -> Self::State {
+        self.get_states()
+            .iter()
+            .filter(|state| !self.is_state_terminal(state))
+            .choose(&mut thread_rng())
+            .expect("MDP must contain at least one non-terminal state.")
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState(usize);
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction;
+    impl Action for TestAction {}
+
+    struct TestMDP {
+        states: Sampler<TestState>,
+        actions: Sampler<TestAction>,
+    }
+
+    impl MDP for TestMDP {
+        type State = TestState;
+        type Action = TestAction;
+
+        fn get_states(&self) -> &Sampler<Self::State> {
+            &self.states
+        }
+
+        fn get_actions(&self) -> &Sampler<Self::Action> {
+            &self.actions
+        }
+
+        fn is_state_terminal(&self, state: &Self::State) -> bool {
+            state.0 == 0
+        }
+
+        fn transition(&self, _state: &Self::State, _action: &Self::Action) -> (Self::State, f64) {
+            (TestState(0), 1.0)
+        }
+    }
+
+    #[test]
+    fn initial_state_is_not_terminal() {
+        let mdp = TestMDP {
+            states: Sampler::new(vec![TestState(0), TestState(1)]),
+            actions: Sampler::new(vec![TestAction]),
+        };
+        for _ in 0..100 {
+            assert_eq!(mdp.get_initial_state(), TestState(1));
+        }
+    }
 }
@@ -5,17 +5,44 @@
 mod bandit;
 pub use bandit::*;
 
+mod basis;
+pub use basis::*;
+
 mod config;
 pub use config::*;
 
+mod environment;
+pub use environment::*;
+
+mod exploration;
+pub use exploration::*;
+
+mod features;
+pub use features::*;
+
+mod linear;
+pub use linear::*;
+
 mod mdp;
 pub use mdp::*;
 
 mod policy;
 pub use policy::*;
 
+mod replay;
+pub use replay::*;
+
 mod sampler;
 pub use sampler::*;
 
+mod solution;
+pub use solution::*;
+
+mod trajectory;
+pub use trajectory::*;
+
+mod tree;
+pub use tree::*;
+
 mod value;
 pub use value::*;
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use rand::{distributions::WeightedIndex, prelude::*};
+
 use crate::errors::NotFound;
 
-use super::{Action, Sampler, State};
+use super::{Action, ActionValue, Sampler, State, StateValue};
 
 /// # Policy
 ///
@@ -39,3 +41,254 @@ where
         self.0.insert(state.clone(), action.clone());
     }
 }
+
+/// # Stochastic Policy
+///
+/// Represents a mapping from states to probability distributions over actions.
+/// Actions missing from the distribution of a state have a probability of zero.
+#[derive(Debug, Clone)]
+pub struct StochasticPolicy<S, A>(HashMap<S, HashMap<A, f64>>)
+where
+    S: State,
+    A: Action;
+
+impl<S, A> StochasticPolicy<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a new stochastic policy with each state mapped to the uniform distribution.
+    pub fn new(states: &Sampler<S>, actions: &Sampler<A>) -> Self {
+        let probability = 1.0 / actions.iter().count() as f64;
+        let mut map = HashMap::new();
+        for state in states {
+            let distribution = actions
+                .iter()
+                .map(|action| (action.clone(), probability))
+                .collect();
+            map.insert(state.clone(), distribution);
+        }
+        Self(map)
+    }
+
+    /// Returns the probability of selecting the given action in the given state.
+    pub fn get(&self, state: &S, action: &A) -> f64 {
+        self.0
+            .get(state)
+            .unwrap_or_else(|| panic!("{}", NotFound::StateInPolicy))
+            .get(action)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Inserts the given probability for the given state-action pair.
+    /// The probabilities of the actions of a state should sum to one.
+    pub fn insert(&mut self, state: &S, action: &A, probability: f64) {
+        self.0
+            .entry(state.clone())
+            .or_default()
+            .insert(action.clone(), probability);
+    }
+
+    /// Returns an action sampled from the distribution of the given state.
+    pub fn sample(&self, state: &S) -> &A {
+        let entries: Vec<(&A, &f64)> = self
+            .0
+            .get(state)
+            .unwrap_or_else(|| panic!("{}", NotFound::StateInPolicy))
+            .iter()
+            .collect();
+        let distribution = WeightedIndex::new(entries.iter().map(|(_, probability)| **probability))
+            .expect("policy probabilities must be non-negative and not all zero.");
+        entries[distribution.sample(&mut thread_rng())].0
+    }
+
+    /// Returns the most likely action in the given state.
+    pub fn get_most_likely(&self, state: &S) -> &A {
+        let (best_action, _) = self
+            .0
+            .get(state)
+            .unwrap_or_else(|| panic!("{}", NotFound::StateInPolicy))
+            .iter()
+            .reduce(|(best_action, best_probability), (action, probability)| {
+                if probability > best_probability {
+                    (action, probability)
+                } else {
+                    (best_action, best_probability)
+                }
+            })
+            .expect("policy must contain at least one action per state.");
+        best_action
+    }
+
+    /// Returns a deterministic policy that maps each state to its most likely action.
+    pub fn most_likely_policy(&self, states: &Sampler<S>, actions: &Sampler<A>) -> Policy<S, A> {
+        let mut policy = Policy::new(states, actions);
+        for state in states {
+            policy.insert(state, self.get_most_likely(state));
+        }
+        policy
+    }
+}
+
+impl<S, A> From<&Policy<S, A>> for StochasticPolicy<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a stochastic policy that selects the action of the given policy
+    /// with probability one.
+    fn from(policy: &Policy<S, A>) -> Self {
+        let mut stochastic_policy = Self(HashMap::new());
+        for (state, action) in &policy.0 {
+            stochastic_policy.insert(state, action, 1.0);
+        }
+        stochastic_policy
+    }
+}
+
+/// # Policy Mixture
+///
+/// Represents a mixture of deterministic policies.
+/// A policy of the mixture is sampled according to its weight at the start of an episode
+/// and followed until its end.
+#[derive(Debug)]
+pub struct PolicyMixture<S, A>
+where
+    S: State,
+    A: Action,
+{
+    policies: Vec<Policy<S, A>>,
+    weights: Vec<f64>,
+}
+
+impl<S, A> PolicyMixture<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a new mixture of the given policies with the given weights.
+    /// The weights should be non-negative and sum to one.
+    pub fn new(policies: Vec<Policy<S, A>>, weights: Vec<f64>) -> Self {
+        assert!(!policies.is_empty(), "mixture must contain a policy.");
+        assert_eq!(
+            policies.len(),
+            weights.len(),
+            "there must be one weight per policy."
+        );
+        Self { policies, weights }
+    }
+
+    /// Returns the policies of the mixture.
+    pub fn get_policies(&self) -> &[Policy<S, A>] {
+        &self.policies
+    }
+
+    /// Returns the weights of the policies of the mixture.
+    pub fn get_weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the policy with the highest weight.
+    pub fn get_most_likely(&self) -> &Policy<S, A> {
+        let (index, _) = self
+            .weights
+            .iter()
+            .enumerate()
+            .reduce(|best, other| if other.1 > best.1 { other } else { best })
+            // unwrap is safe because the mixture is not empty
+            .unwrap();
+        &self.policies[index]
+    }
+
+    /// Returns a policy sampled according to the weights.
+    pub fn sample(&self) -> &Policy<S, A> {
+        let distribution = WeightedIndex::new(&self.weights)
+            .expect("mixture weights must be non-negative and not all zero.");
+        &self.policies[distribution.sample(&mut thread_rng())]
+    }
+}
+
+/// A stochastic policy along with a state value,
+/// such as the baseline or critic it was learned with.
+pub type PolicyAndValue<S, A> = (StochasticPolicy<S, A>, StateValue<S>);
+
+/// A stochastic policy along with the action value it was derived from.
+pub type PolicyAndActionValue<S, A> = (StochasticPolicy<S, A>, ActionValue<S, A>);
+
+/// The weights of a linear reward, such as one learned from demonstrations,
+/// along with a stochastic policy optimizing it.
+pub type WeightsAndPolicy<S, A> = (Vec<f64>, StochasticPolicy<S, A>);
+
+/// The weights of a linear reward, such as one learned from demonstrations,
+/// along with a mixture of policies optimizing it.
+pub type WeightsAndMixture<S, A> = (Vec<f64>, PolicyMixture<S, A>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState(usize);
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction(usize);
+    impl Action for TestAction {}
+
+    fn get_states() -> Sampler<TestState> {
+        Sampler::new(vec![TestState(0), TestState(1)])
+    }
+
+    fn get_actions() -> Sampler<TestAction> {
+        Sampler::new(vec![TestAction(0), TestAction(1)])
+    }
+
+    #[test]
+    fn stochastic_policy_is_uniform_by_default() {
+        let policy = StochasticPolicy::new(&get_states(), &get_actions());
+        assert_eq!(policy.get(&TestState(1), &TestAction(0)), 0.5);
+        assert_eq!(policy.get(&TestState(1), &TestAction(2)), 0.0);
+    }
+
+    #[test]
+    fn stochastic_policy_sample() {
+        let mut policy = StochasticPolicy::new(&get_states(), &get_actions());
+        policy.insert(&TestState(0), &TestAction(0), 0.0);
+        policy.insert(&TestState(0), &TestAction(1), 1.0);
+        for _ in 0..100 {
+            assert_eq!(policy.sample(&TestState(0)), &TestAction(1));
+        }
+        let deterministic = policy.most_likely_policy(&get_states(), &get_actions());
+        assert_eq!(deterministic.get(&TestState(0)), &TestAction(1));
+    }
+
+    #[test]
+    fn stochastic_policy_from_policy() {
+        let mut policy = Policy::new(&get_states(), &get_actions());
+        policy.insert(&TestState(0), &TestAction(1));
+        let stochastic_policy = StochasticPolicy::from(&policy);
+        assert_eq!(stochastic_policy.get(&TestState(0), &TestAction(1)), 1.0);
+        assert_eq!(stochastic_policy.get(&TestState(0), &TestAction(0)), 0.0);
+    }
+
+    #[test]
+    fn policy_mixture_sample() {
+        let mut first = Policy::new(&get_states(), &get_actions());
+        first.insert(&TestState(0), &TestAction(0));
+        let mut second = Policy::new(&get_states(), &get_actions());
+        second.insert(&TestState(0), &TestAction(1));
+        let mixture = PolicyMixture::new(vec![first, second], vec![0.0, 1.0]);
+        for _ in 0..100 {
+            assert_eq!(mixture.sample().get(&TestState(0)), &TestAction(1));
+        }
+        assert_eq!(mixture.get_most_likely().get(&TestState(0)), &TestAction(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "state")]
+    fn unknown_state_in_stochastic_policy() {
+        let policy = StochasticPolicy::new(&get_states(), &get_actions());
+        policy.sample(&TestState(2));
+    }
+}
@@ -0,0 +1,265 @@
+use rand::{distributions::WeightedIndex, prelude::*};
+
+use super::{Action, State, Transition};
+
+/// # Replay Buffer
+///
+/// Represents a bounded memory of transitions
+/// from which minibatches can be sampled for off-policy learning.
+/// When the buffer is full, the oldest transitions are overwritten.
+pub trait ReplayBuffer<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Stores the given transition in the buffer.
+    fn push(&mut self, transition: Transition<S, A>);
+
+    /// Samples a minibatch of the given size with replacement
+    /// and returns the sampled transitions along with their indices.
+    fn sample(&self, batch_size: usize) -> Vec<(usize, Transition<S, A>)>;
+
+    /// Updates the priorities of the transitions at the given indices
+    /// with their latest temporal difference errors.
+    /// Does nothing by default.
+    fn update_priorities(&mut self, _indices: &[usize], _td_errors: &[f64]) {}
+
+    /// Returns the number of transitions in the buffer.
+    fn len(&self) -> usize;
+
+    /// Returns whether the buffer contains no transition.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+fn check_capacity(capacity: usize) {
+    assert!(capacity > 0, "replay buffer capacity must be positive.");
+}
+
+/// Inserts the transition at the given position of a ring buffer
+/// and returns the index it was stored at.
+fn insert<T>(items: &mut Vec<T>, position: &mut usize, capacity: usize, item: T) -> usize {
+    let index = *position;
+    if items.len() < capacity {
+        items.push(item);
+    } else {
+        items[index] = item;
+    }
+    *position = (index + 1) % capacity;
+    index
+}
+
+/// # Uniform Replay
+///
+/// A replay buffer from which transitions are sampled uniformly at random.
+#[derive(Debug, Clone)]
+pub struct UniformReplay<S, A>
+where
+    S: State,
+    A: Action,
+{
+    capacity: usize,
+    position: usize,
+    transitions: Vec<Transition<S, A>>,
+}
+
+impl<S, A> UniformReplay<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a new empty buffer holding at most `capacity` transitions.
+    pub fn new(capacity: usize) -> Self {
+        check_capacity(capacity);
+        Self {
+            capacity,
+            position: 0,
+            transitions: Vec::with_capacity(capacity),
+        }
+    }
+}
+
+impl<S, A> ReplayBuffer<S, A> for UniformReplay<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn push(&mut self, transition: Transition<S, A>) {
+        insert(
+            &mut self.transitions,
+            &mut self.position,
+            self.capacity,
+            transition,
+        );
+    }
+
+    fn sample(&self, batch_size: usize) -> Vec<(usize, Transition<S, A>)> {
+        assert!(!self.is_empty(), "cannot sample from an empty buffer.");
+        let mut rng = thread_rng();
+        (0..batch_size)
+            .map(|_| {
+                let index = rng.gen_range(0..self.transitions.len());
+                (index, self.transitions[index].clone())
+            })
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.transitions.len()
+    }
+}
+
+/// # Prioritized Replay
+///
+/// A replay buffer from which transitions are sampled
+/// with probability proportional to their priority raised to the priority exponent.
+/// The priority of a transition is the absolute value of its last temporal difference error.
+/// New transitions get the highest priority seen so far so that they are replayed at least once.
+/// A priority exponent of zero gives uniform sampling.
+#[derive(Debug, Clone)]
+pub struct PrioritizedReplay<S, A>
+where
+    S: State,
+    A: Action,
+{
+    capacity: usize,
+    priority_exponent: f64,
+    position: usize,
+    max_priority: f64,
+    transitions: Vec<Transition<S, A>>,
+    priorities: Vec<f64>,
+}
+
+impl<S, A> PrioritizedReplay<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Small constant added to priorities so that every transition can be sampled.
+    const MIN_PRIORITY: f64 = 1e-6;
+
+    /// Creates a new empty buffer holding at most `capacity` transitions
+    /// with the given priority exponent.
+    pub fn new(capacity: usize, priority_exponent: f64) -> Self {
+        check_capacity(capacity);
+        Self {
+            capacity,
+            priority_exponent,
+            position: 0,
+            max_priority: 1.0,
+            transitions: Vec::with_capacity(capacity),
+            priorities: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the priority of the transition at the given index.
+    pub fn get_priority(&self, index: usize) -> f64 {
+        self.priorities[index]
+    }
+}
+
+impl<S, A> ReplayBuffer<S, A> for PrioritizedReplay<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn push(&mut self, transition: Transition<S, A>) {
+        let mut position = self.position;
+        insert(
+            &mut self.transitions,
+            &mut position,
+            self.capacity,
+            transition,
+        );
+        insert(
+            &mut self.priorities,
+            &mut self.position,
+            self.capacity,
+            self.max_priority,
+        );
+    }
+
+    fn sample(&self, batch_size: usize) -> Vec<(usize, Transition<S, A>)> {
+        assert!(!self.is_empty(), "cannot sample from an empty buffer.");
+        let weights = self
+            .priorities
+            .iter()
+            .map(|priority| priority.powf(self.priority_exponent));
+        // unwrap is safe because the buffer is not empty and priorities are positive
+        let distribution = WeightedIndex::new(weights).unwrap();
+        let mut rng = thread_rng();
+        (0..batch_size)
+            .map(|_| {
+                let index = distribution.sample(&mut rng);
+                (index, self.transitions[index].clone())
+            })
+            .collect()
+    }
+
+    fn update_priorities(&mut self, indices: &[usize], td_errors: &[f64]) {
+        for (&index, td_error) in indices.iter().zip(td_errors) {
+            let priority = td_error.abs() + Self::MIN_PRIORITY;
+            self.priorities[index] = priority;
+            self.max_priority = self.max_priority.max(priority);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.transitions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState(usize);
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction;
+    impl Action for TestAction {}
+
+    fn get_transition(i: usize) -> Transition<TestState, TestAction> {
+        Transition {
+            state: TestState(i),
+            action: TestAction,
+            reward: 0.0,
+            next_state: TestState(i + 1),
+            done: false,
+        }
+    }
+
+    #[test]
+    fn uniform_replay_overwrites_oldest() {
+        let mut buffer = UniformReplay::new(2);
+        for i in 0..3 {
+            buffer.push(get_transition(i));
+        }
+        assert_eq!(buffer.len(), 2);
+        for (_, transition) in buffer.sample(20) {
+            assert_ne!(transition.state, TestState(0));
+        }
+    }
+
+    #[test]
+    fn prioritized_replay_samples_high_priority() {
+        let mut buffer = PrioritizedReplay::new(2, 1.0);
+        buffer.push(get_transition(0));
+        buffer.push(get_transition(1));
+        buffer.update_priorities(&[0, 1], &[0.0, 10.0]);
+        assert_eq!(buffer.get_priority(1), 10.0 + 1e-6);
+        let samples = buffer.sample(20);
+        let count = samples.iter().filter(|(index, _)| *index == 1).count();
+        assert!(count > 15);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty")]
+    fn sample_from_empty_buffer() {
+        let buffer = UniformReplay::<TestState, TestAction>::new(1);
+        buffer.sample(1);
+    }
+}
@@ -0,0 +1,20 @@
+use super::{Action, Policy, State, StateValue};
+
+/// # Partial Solution
+///
+/// Represents the solution of an MDP computed by a heuristic search solver.
+/// Unlike the solutions of the dynamic programming solvers,
+/// the policy and the state value are only defined
+/// on the states the solver visited from its initial state.
+/// The `solved` flag is set when the values of the states
+/// reachable from the initial state by following the policy have converged.
+#[derive(Debug)]
+pub struct PartialSolution<S, A>
+where
+    S: State,
+    A: Action,
+{
+    pub policy: Policy<S, A>,
+    pub state_value: StateValue<S>,
+    pub solved: bool,
+}
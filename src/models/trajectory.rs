@@ -0,0 +1,246 @@
+use std::slice;
+
+use super::{Action, Environment, Policy, Sampler, State};
+
+/// # Transition
+///
+/// Represents a single step of interaction with an environment.
+/// The `done` flag is set when the next state is terminal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition<S, A>
+where
+    S: State,
+    A: Action,
+{
+    pub state: S,
+    pub action: A,
+    pub reward: f64,
+    pub next_state: S,
+    pub done: bool,
+}
+
+/// # Episode
+///
+/// Represents the sequence of transitions experienced during an episode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Episode<S, A>(Vec<Transition<S, A>>)
+where
+    S: State,
+    A: Action;
+
+impl<S, A> Episode<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a new empty episode.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Plays the given policy in the environment for at most `max_num_steps` steps
+    /// and returns the resulting episode.
+    pub fn rollout<E>(env: &mut E, policy: &Policy<S, A>, max_num_steps: u32) -> Self
+    where
+        E: Environment<State = S, Action = A>,
+    {
+        let mut episode = Self::new();
+        let mut state = env.reset();
+        for _ in 0..max_num_steps {
+            let action = policy.get(&state).clone();
+            let (next_state, reward, done, truncated) = env.step(&action);
+            episode.push(Transition {
+                state,
+                action,
+                reward,
+                next_state: next_state.clone(),
+                done,
+            });
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+        episode
+    }
+
+    /// Appends the given transition to the episode.
+    pub fn push(&mut self, transition: Transition<S, A>) {
+        self.0.push(transition);
+    }
+
+    /// Returns the number of transitions in the episode.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the episode contains no transition.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the discounted sum of the rewards of the episode.
+    pub fn get_return(&self, discount_factor: f64) -> f64 {
+        self.0.iter().rev().fold(0.0, |total, transition| {
+            transition.reward + discount_factor * total
+        })
+    }
+
+    /// Returns an iterator over references to the transitions of the episode.
+    pub fn iter(&self) -> slice::Iter<'_, Transition<S, A>> {
+        self.0.iter()
+    }
+}
+
+impl<S, A> Default for Episode<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S, A> IntoIterator for &'a Episode<S, A>
+where
+    S: State,
+    A: Action,
+{
+    type Item = &'a Transition<S, A>;
+    type IntoIter = slice::Iter<'a, Transition<S, A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S, A> From<Vec<Transition<S, A>>> for Episode<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn from(transitions: Vec<Transition<S, A>>) -> Self {
+        Self(transitions)
+    }
+}
+
+/// # Recorder
+///
+/// Wraps an environment and records every episode played in it.
+/// Since the recorder is itself an [`Environment`],
+/// it can be given to any solver or rollout to capture its experience.
+/// A new episode is started each time the environment is reset.
+pub struct Recorder<E>
+where
+    E: Environment,
+{
+    env: E,
+    state: Option<E::State>,
+    episodes: Vec<Episode<E::State, E::Action>>,
+}
+
+impl<E> Recorder<E>
+where
+    E: Environment,
+{
+    /// Creates a new recorder around the given environment.
+    pub fn new(env: E) -> Self {
+        Self {
+            env,
+            state: None,
+            episodes: Vec::new(),
+        }
+    }
+
+    /// Returns the recorded episodes, including the current one.
+    pub fn get_episodes(&self) -> &[Episode<E::State, E::Action>] {
+        &self.episodes
+    }
+
+    /// Consumes the recorder and returns the recorded episodes.
+    pub fn into_episodes(self) -> Vec<Episode<E::State, E::Action>> {
+        self.episodes
+    }
+}
+
+impl<E> Environment for Recorder<E>
+where
+    E: Environment,
+{
+    type State = E::State;
+    type Action = E::Action;
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        self.env.get_actions()
+    }
+
+    fn reset(&mut self) -> Self::State {
+        let state = self.env.reset();
+        self.state = Some(state.clone());
+        self.episodes.push(Episode::new());
+        state
+    }
+
+    fn step(&mut self, action: &Self::Action) -> (Self::State, f64, bool, bool) {
+        let state = self
+            .state
+            .take()
+            .expect("environment must be reset before stepping.");
+        let (next_state, reward, done, truncated) = self.env.step(action);
+        // unwrap is safe because an episode is pushed on reset
+        self.episodes.last_mut().unwrap().push(Transition {
+            state,
+            action: action.clone(),
+            reward,
+            next_state: next_state.clone(),
+            done,
+        });
+        self.state = Some(next_state.clone());
+        (next_state, reward, done, truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        get_gridworld, get_optimal_policy, GridworldAction, GridworldState,
+    };
+    use crate::models::{MDPEnvironment, MDP};
+
+    #[test]
+    fn rollout_optimal_policy() {
+        let mdp = get_gridworld().initial_states(vec![GridworldState::new(0, 0)]);
+        let policy = get_optimal_policy(mdp.get_states(), mdp.get_actions());
+        let mut env = MDPEnvironment::new(&mdp);
+        let episode = Episode::rollout(&mut env, &policy, 10);
+        assert_eq!(episode.len(), 2);
+        assert!(episode.iter().last().unwrap().done);
+        assert_eq!(episode.get_return(0.5), -1.0 + 0.5 * 100.0);
+    }
+
+    #[test]
+    fn recorder_records_episodes() {
+        let mdp = get_gridworld().initial_states(vec![GridworldState::new(0, 0)]);
+        let mut recorder = Recorder::new(MDPEnvironment::new(&mdp));
+        recorder.reset();
+        recorder.step(&GridworldAction::Up);
+        recorder.reset();
+        recorder.step(&GridworldAction::Right);
+        recorder.step(&GridworldAction::Down);
+        let episodes = recorder.into_episodes();
+        assert_eq!(episodes.len(), 2);
+        assert_eq!(episodes[0].len(), 1);
+        assert_eq!(
+            episodes[1].iter().next().unwrap(),
+            &Transition {
+                state: GridworldState::new(0, 0),
+                action: GridworldAction::Right,
+                reward: -1.0,
+                next_state: GridworldState::new(0, 1),
+                done: false,
+            }
+        );
+    }
+}
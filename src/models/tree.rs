@@ -0,0 +1,188 @@
+use std::collections::{hash_map, HashMap};
+
+use super::{Action, Sampler, State};
+
+/// # Action Statistics
+///
+/// Represents the statistics of an action in a node of a search tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionStatistics {
+    pub num_visits: u32,
+    pub total_return: f64,
+}
+
+impl ActionStatistics {
+    /// Returns the average return following the action,
+    /// or zero if it has never been visited.
+    pub fn get_mean_return(&self) -> f64 {
+        if self.num_visits == 0 {
+            0.0
+        } else {
+            self.total_return / f64::from(self.num_visits)
+        }
+    }
+}
+
+/// # Search Node
+///
+/// Represents the statistics of a state in a search tree:
+/// its number of visits and the statistics of each of its actions.
+#[derive(Debug, Clone)]
+pub struct SearchNode<A>
+where
+    A: Action,
+{
+    num_visits: u32,
+    actions: HashMap<A, ActionStatistics>,
+}
+
+impl<A> SearchNode<A>
+where
+    A: Action,
+{
+    /// Creates a new node with each action never visited.
+    pub fn new(actions: &Sampler<A>) -> Self {
+        let actions = actions
+            .iter()
+            .map(|action| (action.clone(), ActionStatistics::default()))
+            .collect();
+        Self {
+            num_visits: 0,
+            actions,
+        }
+    }
+
+    /// Returns the number of visits of the node.
+    pub fn get_num_visits(&self) -> u32 {
+        self.num_visits
+    }
+
+    /// Returns the statistics of the given action, if it belongs to the node.
+    pub fn get(&self, action: &A) -> Option<&ActionStatistics> {
+        self.actions.get(action)
+    }
+
+    /// Returns an iterator over the actions and their statistics.
+    pub fn iter(&self) -> hash_map::Iter<'_, A, ActionStatistics> {
+        self.actions.iter()
+    }
+
+    /// Returns the most visited action.
+    pub fn most_visited(&self) -> &A {
+        let (best_action, _) = self
+            .actions
+            .iter()
+            .reduce(|best, other| {
+                if other.1.num_visits > best.1.num_visits {
+                    other
+                } else {
+                    best
+                }
+            })
+            .expect("search node must contain at least one action.");
+        best_action
+    }
+
+    /// Records a visit of the given action followed by the given return.
+    pub fn update(&mut self, action: &A, return_: f64) {
+        self.num_visits += 1;
+        let statistics = self.actions.entry(action.clone()).or_default();
+        statistics.num_visits += 1;
+        statistics.total_return += return_;
+    }
+}
+
+/// # Search Tree
+///
+/// Represents the statistics gathered by a tree search planner
+/// for each state it expanded.
+/// Since transitions are deterministic, a state reached along different paths
+/// shares a single node.
+#[derive(Debug, Clone)]
+pub struct SearchTree<S, A>
+where
+    S: State,
+    A: Action,
+{
+    nodes: HashMap<S, SearchNode<A>>,
+}
+
+impl<S, A> SearchTree<S, A>
+where
+    S: State,
+    A: Action,
+{
+    /// Creates a new empty search tree.
+    pub fn new() -> Self {
+        Self {
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Returns the node of the given state, if it has been expanded.
+    pub fn get(&self, state: &S) -> Option<&SearchNode<A>> {
+        self.nodes.get(state)
+    }
+
+    /// Returns a mutable reference to the node of the given state, if it has been expanded.
+    pub fn get_mut(&mut self, state: &S) -> Option<&mut SearchNode<A>> {
+        self.nodes.get_mut(state)
+    }
+
+    /// Expands the given state with a new node for the given actions.
+    pub fn expand(&mut self, state: &S, actions: &Sampler<A>) {
+        self.nodes
+            .entry(state.clone())
+            .or_insert_with(|| SearchNode::new(actions));
+    }
+
+    /// Returns the number of expanded states.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns whether no state has been expanded.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl<S, A> Default for SearchTree<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestState(usize);
+    impl State for TestState {}
+
+    #[derive(PartialEq, Eq, Hash, Clone, Debug)]
+    struct TestAction(usize);
+    impl Action for TestAction {}
+
+    #[test]
+    fn search_tree_statistics() {
+        let actions = Sampler::new(vec![TestAction(0), TestAction(1)]);
+        let mut tree = SearchTree::new();
+        assert!(tree.get(&TestState(0)).is_none());
+        tree.expand(&TestState(0), &actions);
+        let node = tree.get_mut(&TestState(0)).unwrap();
+        node.update(&TestAction(1), 2.0);
+        node.update(&TestAction(1), 4.0);
+        node.update(&TestAction(0), 1.0);
+        let node = tree.get(&TestState(0)).unwrap();
+        assert_eq!(node.get_num_visits(), 3);
+        assert_eq!(node.get(&TestAction(1)).unwrap().get_mean_return(), 3.0);
+        assert_eq!(node.most_visited(), &TestAction(1));
+        assert_eq!(tree.len(), 1);
+    }
+}
@@ -1,10 +1,10 @@
-use rand::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::*};
 
 use crate::errors::NotFound;
 
-use super::{Action, Policy, Sampler, State};
+use super::{Action, Policy, Sampler, State, StochasticPolicy};
 
-use std::collections::HashMap;
+use std::collections::{hash_map, HashMap};
 
 /// # State Value
 ///
@@ -27,6 +27,12 @@ where
         Self(map)
     }
 
+    /// Creates a new state value without any state,
+    /// to be filled as states are visited.
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
     /// Returns the value associated with the given state.
     pub fn get(&self, state: &S) -> f64 {
         *self
@@ -35,6 +41,12 @@ where
             .unwrap_or_else(|| panic!("{}", NotFound::StateInStateValue))
     }
 
+    /// Returns the value associated with the given state,
+    /// after mapping it to the given value if it is not present yet.
+    pub fn get_or_insert(&mut self, state: &S, value: f64) -> f64 {
+        *self.0.entry(state.clone()).or_insert(value)
+    }
+
     /// Inserts the given value for the given state.
     pub fn insert(&mut self, state: &S, value: f64) {
         self.0.insert(state.clone(), value);
@@ -55,9 +67,14 @@ where
 {
     /// Creates a new state action value with each action mapped to zero.
     pub fn new(actions: &Sampler<A>) -> Self {
+        Self::with_value(actions, 0.0)
+    }
+
+    /// Creates a new state action value with each action mapped to the given value.
+    pub fn with_value(actions: &Sampler<A>, value: f64) -> Self {
         let mut map = HashMap::new();
         for action in actions {
-            map.insert(action.clone(), 0.0);
+            map.insert(action.clone(), value);
         }
         Self(map)
     }
@@ -75,6 +92,11 @@ where
         self.0.insert(action.clone(), value);
     }
 
+    /// Returns an iterator over the actions and their values.
+    pub fn iter(&self) -> hash_map::Iter<'_, A, f64> {
+        self.0.iter()
+    }
+
     /// Returns the action with the highest value.
     pub fn greedy(&self) -> &A {
         let (best_action, _) = self
@@ -94,13 +116,63 @@ where
 
     /// Returns a random action with probability epsilon
     /// or the greedy action with probability 1 - epsilon.
-    pub fn epsilon_greedy<'a>(&'a self, actions: &'a Sampler<A>, epsilon: f64) -> &A {
+    pub fn epsilon_greedy<'a>(&'a self, actions: &'a Sampler<A>, epsilon: f64) -> &'a A {
         if random::<f64>() < epsilon {
             actions.get_random()
         } else {
             self.greedy()
         }
     }
+
+    /// Returns an action sampled from the Boltzmann distribution
+    /// over the values with the given temperature.
+    /// The lower the temperature, the closer it is to the greedy action.
+    pub fn softmax(&self, temperature: f64) -> &A {
+        let weights = self.get_boltzmann_weights(temperature);
+        // unwrap is safe because the map is not empty
+        // and the weight of the best action is one
+        let distribution = WeightedIndex::new(weights.iter().map(|(_, weight)| weight)).unwrap();
+        weights[distribution.sample(&mut thread_rng())].0
+    }
+
+    /// Returns the log-sum-exp of the values with the given temperature,
+    /// `temperature * ln(sum(exp(value / temperature)))`,
+    /// which is a soft maximum of the values.
+    /// The lower the temperature, the closer it is to the maximum.
+    pub fn log_sum_exp(&self, temperature: f64) -> f64 {
+        let sum: f64 = self
+            .get_boltzmann_weights(temperature)
+            .iter()
+            .map(|(_, weight)| weight)
+            .sum();
+        self.get_max_value() + temperature * sum.ln()
+    }
+
+    /// Returns the probabilities of the Boltzmann distribution
+    /// over the values with the given temperature.
+    pub fn softmax_probabilities(&self, temperature: f64) -> HashMap<A, f64> {
+        let weights = self.get_boltzmann_weights(temperature);
+        let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        weights
+            .into_iter()
+            .map(|(action, weight)| (action.clone(), weight / total))
+            .collect()
+    }
+
+    /// Returns the highest value.
+    fn get_max_value(&self) -> f64 {
+        self.0.values().copied().fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Returns the unnormalized Boltzmann weights of the values with the given temperature,
+    /// shifted by the highest value so that they cannot overflow.
+    fn get_boltzmann_weights(&self, temperature: f64) -> Vec<(&A, f64)> {
+        let max_value = self.get_max_value();
+        self.0
+            .iter()
+            .map(|(action, value)| (action, ((value - max_value) / temperature).exp()))
+            .collect()
+    }
 }
 
 /// # Action Value
@@ -119,13 +191,49 @@ where
 {
     /// Creates a new action value with each state-action pair mapped to zero.
     pub fn new(states: &Sampler<S>, actions: &Sampler<A>) -> Self {
+        Self::with_value(states, actions, 0.0)
+    }
+
+    /// Creates a new action value with each state-action pair mapped to the given value.
+    pub fn with_value(states: &Sampler<S>, actions: &Sampler<A>, value: f64) -> Self {
         let mut map = HashMap::new();
         for state in states {
-            map.insert(state.clone(), StateActionValue::new(actions));
+            map.insert(state.clone(), StateActionValue::with_value(actions, value));
         }
         Self(map)
     }
 
+    /// Creates a new action value without any state,
+    /// to be filled as states are visited.
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Returns the action values associated with the given state.
+    pub fn get_state_action_value(&self, state: &S) -> &StateActionValue<A> {
+        self.0
+            .get(state)
+            .unwrap_or_else(|| panic!("{}", NotFound::StateInActionValue))
+    }
+
+    /// Returns the action values associated with the given state,
+    /// after mapping each of the given actions to the given value if the state is not present yet.
+    pub fn get_or_insert_state_action_value(
+        &mut self,
+        state: &S,
+        actions: &Sampler<A>,
+        value: f64,
+    ) -> &StateActionValue<A> {
+        self.0
+            .entry(state.clone())
+            .or_insert_with(|| StateActionValue::with_value(actions, value))
+    }
+
+    /// Returns the states present in the action value, in no particular order.
+    pub fn get_states(&self) -> Vec<S> {
+        self.0.keys().cloned().collect()
+    }
+
     /// Returns the value associated with the given state-action pair.
     pub fn get(&self, state: &S, action: &A) -> f64 {
         self.0
@@ -144,27 +252,48 @@ where
 
     /// Returns the action with the highest value for the given state.
     pub fn greedy(&self, state: &S) -> &A {
-        self.0
-            .get(state)
-            .unwrap_or_else(|| panic!("{}", NotFound::StateInActionValue))
-            .greedy()
+        self.get_state_action_value(state).greedy()
     }
 
     /// For a given state, returns the action
     /// with the highest value with probability 1 - epsilon
     /// or a random action with probability epsilon.
-    pub fn epsilon_greedy<'a>(&'a self, actions: &'a Sampler<A>, state: &S, epsilon: f64) -> &A {
-        self.0
-            .get(state)
-            .unwrap_or_else(|| panic!("{}", NotFound::StateInActionValue))
+    pub fn epsilon_greedy<'a>(&'a self, actions: &'a Sampler<A>, state: &S, epsilon: f64) -> &'a A {
+        self.get_state_action_value(state)
             .epsilon_greedy(actions, epsilon)
     }
 
     /// Returns a policy that maps each state to the action with the highest value.
+    /// States missing from the action value, for instance states a solver never reached,
+    /// keep a random action.
     pub fn greedy_policy(&self, states: &Sampler<S>, actions: &Sampler<A>) -> Policy<S, A> {
         let mut policy = Policy::new(states, actions);
         for state in states {
-            policy.insert(state, self.greedy(state));
+            if let Some(state_action_value) = self.0.get(state) {
+                policy.insert(state, state_action_value.greedy());
+            }
+        }
+        policy
+    }
+
+    /// Returns a stochastic policy that maps each state to the Boltzmann distribution
+    /// over its action values with the given temperature.
+    /// States missing from the action value keep the uniform distribution.
+    pub fn boltzmann_policy(
+        &self,
+        states: &Sampler<S>,
+        actions: &Sampler<A>,
+        temperature: f64,
+    ) -> StochasticPolicy<S, A> {
+        let mut policy = StochasticPolicy::new(states, actions);
+        for state in states {
+            let Some(state_action_value) = self.0.get(state) else {
+                continue;
+            };
+            let probabilities = state_action_value.softmax_probabilities(temperature);
+            for (action, probability) in &probabilities {
+                policy.insert(state, action, *probability);
+            }
         }
         policy
     }
@@ -219,6 +348,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn state_action_value_softmax() {
+        assert_eq!(get_state_action_value().softmax(1e-3), &TestAction(1));
+    }
+
+    #[test]
+    fn state_action_value_softmax_probabilities() {
+        let probabilities = get_state_action_value().softmax_probabilities(1.0);
+        let expected = 1.0 / (1.0 + (-1.0_f64).exp());
+        assert!((probabilities[&TestAction(1)] - expected).abs() < 1e-12);
+        assert!((probabilities[&TestAction(0)] - (1.0 - expected)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn state_action_value_log_sum_exp() {
+        let state_action_value = get_state_action_value();
+        let expected = (1.0 + 1.0_f64.exp()).ln();
+        assert!((state_action_value.log_sum_exp(1.0) - expected).abs() < 1e-12);
+        assert!((state_action_value.log_sum_exp(1e-3) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn action_value_boltzmann_policy() {
+        let policy = get_action_value().boltzmann_policy(&get_states(), &get_actions(), 1e-3);
+        assert!((policy.get(&TestState(0), &TestAction(1)) - 1.0).abs() < 1e-12);
+        assert!((policy.get(&TestState(1), &TestAction(0)) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn action_value_with_value() {
+        let action_value = ActionValue::with_value(&get_states(), &get_actions(), 5.0);
+        assert_eq!(action_value.get(&TestState(1), &TestAction(0)), 5.0);
+    }
+
+    #[test]
+    fn action_value_lazy_states() {
+        let mut action_value = ActionValue::empty();
+        let state_action_value =
+            action_value.get_or_insert_state_action_value(&TestState(2), &get_actions(), 5.0);
+        assert_eq!(state_action_value.get(&TestAction(1)), 5.0);
+        action_value.insert(&TestState(2), &TestAction(1), 1.0);
+        action_value.get_or_insert_state_action_value(&TestState(2), &get_actions(), 5.0);
+        assert_eq!(action_value.get(&TestState(2), &TestAction(1)), 1.0);
+        assert_eq!(action_value.get_states(), vec![TestState(2)]);
+    }
+
+    #[test]
+    fn policies_of_missing_states() {
+        let mut action_value = ActionValue::empty();
+        action_value.get_or_insert_state_action_value(&TestState(0), &get_actions(), 0.0);
+        action_value.insert(&TestState(0), &TestAction(1), 1.0);
+        let policy = action_value.greedy_policy(&get_states(), &get_actions());
+        assert_eq!(policy.get(&TestState(0)), &TestAction(1));
+        assert!(get_actions()
+            .iter()
+            .any(|action| policy.get(&TestState(1)) == action));
+        let policy = action_value.boltzmann_policy(&get_states(), &get_actions(), 1.0);
+        assert_eq!(policy.get(&TestState(1), &TestAction(0)), 0.5);
+    }
+
+    #[test]
+    fn state_value_lazy_states() {
+        let mut state_value = StateValue::empty();
+        assert_eq!(state_value.get_or_insert(&TestState(2), 5.0), 5.0);
+        state_value.insert(&TestState(2), 1.0);
+        assert_eq!(state_value.get_or_insert(&TestState(2), 5.0), 1.0);
+    }
+
     #[test]
     fn action_value_greedy_policy() {
         let policy = get_action_value().greedy_policy(&get_states(), &get_actions());
@@ -0,0 +1,255 @@
+//! # approximation
+//!
+//! The `approximation` module contains the implementations of the temporal difference algorithms
+//! with linear function approximation.
+//! Instead of a table, the solvers learn the weights of a linear function of features,
+//! which makes them usable on problems with too many states to enumerate.
+//! The solvers only interact with the problem through the [`Environment`] trait.
+
+use crate::models::{
+    Config, Environment, Exploration, LinearActionValue, LinearStateValue, StateActionFeatures,
+    StateFeatures, StochasticPolicy,
+};
+
+/// # Semi-Gradient TD(0)
+///
+/// This function implements the semi-gradient TD(0) algorithm
+/// to evaluate the given policy with a linear state value.
+/// At each step, the weights are moved in the direction of the features of the current state
+/// by the temporal difference error times the learning rate.
+/// The gradient of the target is ignored, hence the name semi-gradient.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+pub fn semi_gradient_td<E, F, P>(
+    env: &mut E,
+    config: &Config,
+    features: F,
+    mut policy: P,
+) -> LinearStateValue<E::State, F>
+where
+    E: Environment,
+    F: StateFeatures<E::State>,
+    P: FnMut(&E::State) -> E::Action,
+{
+    let mut state_value = LinearStateValue::new(features);
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = policy(&state);
+            let (next_state, reward, done, truncated) = env.step(&action);
+            let next_value = if done {
+                0.0
+            } else {
+                state_value.get(&next_state)
+            };
+            let td_error = reward + config.discount_factor * next_value - state_value.get(&state);
+            state_value.update(&state, config.learning_rate * td_error);
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    state_value
+}
+
+/// # Off-Policy Semi-Gradient TD(0)
+///
+/// This function implements the off-policy semi-gradient TD(0) algorithm
+/// to evaluate the target policy while following the behaviour policy.
+/// It works like [`semi_gradient_td`],
+/// except that each update is weighted by the importance sampling ratio
+/// of the target and behaviour probabilities of the action taken.
+/// The learning starts from the given linear state value.
+/// This method is not guaranteed to converge and can diverge,
+/// as demonstrated by [Baird's counterexample](crate::environments::baird).
+/// The [gradient temporal difference](super::gradient_temporal_difference) methods
+/// are stable alternatives.
+pub fn off_policy_semi_gradient_td<E, F>(
+    env: &mut E,
+    config: &Config,
+    mut state_value: LinearStateValue<E::State, F>,
+    target: &StochasticPolicy<E::State, E::Action>,
+    behaviour: &StochasticPolicy<E::State, E::Action>,
+) -> LinearStateValue<E::State, F>
+where
+    E: Environment,
+    F: StateFeatures<E::State>,
+{
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = behaviour.sample(&state).clone();
+            let (next_state, reward, done, truncated) = env.step(&action);
+            let ratio = target.get(&state, &action) / behaviour.get(&state, &action);
+            let next_value = if done {
+                0.0
+            } else {
+                state_value.get(&next_state)
+            };
+            let td_error = reward + config.discount_factor * next_value - state_value.get(&state);
+            state_value.update(&state, config.learning_rate * ratio * td_error);
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    state_value
+}
+
+fn semi_gradient_sarsa_q_learning<E, X, F>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+    features: F,
+    q_learning: bool,
+) -> LinearActionValue<E::State, E::Action, F>
+where
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
+    F: StateActionFeatures<E::State, E::Action>,
+{
+    let mut action_value = LinearActionValue::new(features);
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        let mut action = exploration.choose(
+            &state,
+            &action_value.get_state_action_value(&state, env.get_actions()),
+            env.get_actions(),
+        );
+        for _ in 0..config.max_num_steps {
+            let (next_state, reward, done, truncated) = env.step(&action);
+            let next_action = exploration.choose(
+                &next_state,
+                &action_value.get_state_action_value(&next_state, env.get_actions()),
+                env.get_actions(),
+            );
+            // update weights
+            let q_value = if done {
+                0.0
+            } else if q_learning {
+                let greedy_action = action_value.greedy(&next_state, env.get_actions());
+                action_value.get(&next_state, greedy_action)
+            } else {
+                action_value.get(&next_state, &next_action)
+            };
+            let td_error =
+                reward + config.discount_factor * q_value - action_value.get(&state, &action);
+            action_value.update(&state, &action, config.learning_rate * td_error);
+            state = next_state;
+            action = next_action;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    action_value
+}
+
+/// # Semi-Gradient SARSA
+///
+/// This function implements the SARSA algorithm with a linear action value.
+/// It works like [`sarsa`](super::temporal_difference::sarsa),
+/// except that the temporal difference error updates the weights
+/// in the direction of the features of the current state-action pair.
+/// The algorithm uses the given exploration strategy to select actions,
+/// from the approximate action values of the current state.
+/// Since the weights start at zero,
+/// the initial value of the exploration strategy is not used.
+pub fn semi_gradient_sarsa<E, X, F>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+    features: F,
+) -> LinearActionValue<E::State, E::Action, F>
+where
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
+    F: StateActionFeatures<E::State, E::Action>,
+{
+    semi_gradient_sarsa_q_learning(env, config, exploration, features, false)
+}
+
+/// # Semi-Gradient Q-Learning
+///
+/// This function implements the Q-Learning algorithm with a linear action value.
+/// It works like [`q_learning`](super::temporal_difference::q_learning),
+/// except that the temporal difference error updates the weights
+/// in the direction of the features of the current state-action pair.
+/// The algorithm uses the given exploration strategy to select actions,
+/// from the approximate action values of the current state.
+/// Since the weights start at zero,
+/// the initial value of the exploration strategy is not used.
+/// Note that Q-Learning with function approximation is not guaranteed to converge.
+pub fn semi_gradient_q_learning<E, X, F>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+    features: F,
+) -> LinearActionValue<E::State, E::Action, F>
+where
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
+    F: StateActionFeatures<E::State, E::Action>,
+{
+    semi_gradient_sarsa_q_learning(env, config, exploration, features, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        assert_policy_optimal, get_gridworld, get_optimal_policy, get_test_config,
+        get_test_state_value, Gridworld, GridworldAction, GridworldState,
+    };
+    use crate::models::{EpsilonGreedy, MDPEnvironment, OneHot, PerActionFeatures, MDP};
+
+    fn get_features(mdp: &Gridworld) -> PerActionFeatures<OneHot<GridworldState>, GridworldAction> {
+        PerActionFeatures::new(OneHot::new(mdp.get_states()), mdp.get_actions())
+    }
+
+    #[test]
+    fn test_semi_gradient_td() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let policy = get_optimal_policy(mdp.get_states(), mdp.get_actions());
+        let mut env = MDPEnvironment::new(&mdp);
+        let state_value =
+            semi_gradient_td(&mut env, &config, OneHot::new(mdp.get_states()), |state| {
+                policy.get(state).clone()
+            });
+        let expected = get_test_state_value(mdp.get_states());
+        for state in mdp.get_states() {
+            if !mdp.is_state_terminal(state) {
+                assert!((state_value.get(state) - expected.get(state)).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_semi_gradient_sarsa() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value =
+            semi_gradient_sarsa(&mut env, &config, &mut exploration, get_features(&mdp));
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_semi_gradient_q_learning() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value =
+            semi_gradient_q_learning(&mut env, &config, &mut exploration, get_features(&mdp));
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use rand::{distributions::WeightedIndex, prelude::*};
+
+use crate::models::{Action, Sampler, StateActionValue};
+
+use super::BanditAgent;
+
+/// Returns the probability of selecting each arm
+/// from exponential weights `exp(scale * score)`, that is the softmax of the scores
+/// with a temperature of `1 / scale`, mixed with uniform exploration.
+fn get_mixed_probabilities<A>(
+    actions: &Sampler<A>,
+    scores: &StateActionValue<A>,
+    scale: f64,
+    gamma: f64,
+) -> HashMap<A, f64>
+where
+    A: Action,
+{
+    let probabilities = scores.softmax_probabilities(scale.recip());
+    let k = actions.iter().count() as f64;
+    actions
+        .iter()
+        .map(|action| {
+            (
+                action.clone(),
+                (1.0 - gamma) * probabilities[action] + gamma / k,
+            )
+        })
+        .collect()
+}
+
+/// Samples an arm according to the given probabilities.
+fn sample_arm<A>(actions: &Sampler<A>, probabilities: &HashMap<A, f64>) -> A
+where
+    A: Action,
+{
+    let weights = actions.iter().map(|action| probabilities[action]);
+    // unwrap is safe because the probabilities are positive
+    let distribution = WeightedIndex::new(weights).unwrap();
+    // unwrap is safe because the index is within the actions
+    actions
+        .iter()
+        .nth(distribution.sample(&mut thread_rng()))
+        .unwrap()
+        .clone()
+}
+
+/// # EXP3
+///
+/// Exponential-weight algorithm for exploration and exploitation.
+/// Makes no assumption on how the rewards are generated,
+/// which makes it suitable for adversarial bandits.
+/// Arms are sampled from exponential weights of their importance-weighted cumulative rewards,
+/// mixed with uniform exploration of rate `gamma`.
+/// Rewards must lie between zero and one.
+/// The estimates are the importance-weighted cumulative rewards.
+#[derive(Debug, Clone)]
+pub struct EXP3<A>
+where
+    A: Action,
+{
+    gamma: f64,
+    num_arms: f64,
+    estimates: StateActionValue<A>,
+    probabilities: HashMap<A, f64>,
+}
+
+impl<A> EXP3<A>
+where
+    A: Action,
+{
+    /// Creates a new EXP3 agent with the given exploration rate.
+    pub fn new(actions: &Sampler<A>, gamma: f64) -> Self {
+        assert!(
+            gamma > 0.0 && gamma <= 1.0,
+            "gamma must be in (0, 1] for EXP3."
+        );
+        Self {
+            gamma,
+            num_arms: actions.iter().count() as f64,
+            estimates: StateActionValue::new(actions),
+            probabilities: HashMap::new(),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for EXP3<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        self.probabilities = get_mixed_probabilities(
+            actions,
+            &self.estimates,
+            self.gamma / self.num_arms,
+            self.gamma,
+        );
+        sample_arm(actions, &self.probabilities)
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        let probability = self
+            .probabilities
+            .get(action)
+            .copied()
+            .expect("arm must be selected before being updated.");
+        let estimate = self.estimates.get(action);
+        self.estimates
+            .insert(action, estimate + reward / probability);
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+/// # EXP3.P
+///
+/// Variant of [`EXP3`] whose regret bound holds with high probability
+/// rather than only in expectation.
+/// Every arm receives an additional confidence bonus `alpha / (p * sqrt(K * T))`
+/// at each step, where `T` is the known horizon.
+/// Rewards must lie between zero and one.
+/// The estimates are the importance-weighted cumulative rewards plus the bonuses.
+#[derive(Debug, Clone)]
+pub struct EXP3P<A>
+where
+    A: Action,
+{
+    gamma: f64,
+    alpha: f64,
+    num_arms: f64,
+    horizon: f64,
+    estimates: StateActionValue<A>,
+    probabilities: HashMap<A, f64>,
+}
+
+impl<A> EXP3P<A>
+where
+    A: Action,
+{
+    /// Creates a new EXP3.P agent with the given exploration rate,
+    /// confidence parameter and horizon.
+    pub fn new(actions: &Sampler<A>, gamma: f64, alpha: f64, horizon: u32) -> Self {
+        assert!(
+            gamma > 0.0 && gamma <= 1.0,
+            "gamma must be in (0, 1] for EXP3.P."
+        );
+        Self {
+            gamma,
+            alpha,
+            num_arms: actions.iter().count() as f64,
+            horizon: f64::from(horizon),
+            estimates: StateActionValue::new(actions),
+            probabilities: HashMap::new(),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for EXP3P<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        self.probabilities = get_mixed_probabilities(
+            actions,
+            &self.estimates,
+            self.gamma / (3.0 * self.num_arms),
+            self.gamma,
+        );
+        sample_arm(actions, &self.probabilities)
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        assert!(
+            self.probabilities.contains_key(action),
+            "arm must be selected before being updated."
+        );
+        let bonus_scale = self.alpha / (self.num_arms * self.horizon).sqrt();
+        for (other, probability) in &self.probabilities {
+            let reward_estimate = if other == action {
+                reward / probability
+            } else {
+                0.0
+            };
+            let estimate = self.estimates.get(other);
+            self.estimates.insert(
+                other,
+                estimate + reward_estimate + bonus_scale / probability,
+            );
+        }
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::bandit::{AdversarialBandit, Arm};
+    use crate::models::{Bandit, Config};
+    use crate::solvers::bandit::run;
+
+    fn get_bandit() -> AdversarialBandit {
+        // arm 2 is the best fixed arm although it is never the best at a given step
+        AdversarialBandit::new(vec![
+            vec![1.0, 0.0, 0.7],
+            vec![0.0, 1.0, 0.7],
+            vec![0.0, 0.0, 0.7],
+        ])
+    }
+
+    fn assert_low_regret<G>(mut agent: G)
+    where
+        G: BanditAgent<Arm>,
+    {
+        let bandit = get_bandit();
+        let config = Config::new().max_num_steps(3_000);
+        run(&bandit, &mut agent, &config);
+        assert!(bandit.get_regret() < 0.1 * 3_000.0);
+        assert_eq!(agent.get_estimates().greedy(), &Arm(2));
+    }
+
+    #[test]
+    fn test_exp3() {
+        let bandit = get_bandit();
+        assert_low_regret(EXP3::new(bandit.get_actions(), 0.1));
+    }
+
+    #[test]
+    fn test_exp3p() {
+        let bandit = get_bandit();
+        assert_low_regret(EXP3P::new(bandit.get_actions(), 0.1, 1.0, 3_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "selected")]
+    fn update_before_select() {
+        let bandit = get_bandit();
+        let mut agent = EXP3::new(bandit.get_actions(), 0.1);
+        agent.update(&Arm(0), 1.0);
+    }
+}
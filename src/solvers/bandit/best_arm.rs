@@ -0,0 +1,262 @@
+use crate::models::{Bandit, Config};
+
+/// Statistics of the arms of a bandit during pure exploration.
+struct ArmStatistics<A> {
+    arms: Vec<A>,
+    sums: Vec<f64>,
+    counts: Vec<u32>,
+    num_pulls: u32,
+}
+
+impl<A> ArmStatistics<A>
+where
+    A: Clone,
+{
+    fn new<B>(bandit: &B) -> Self
+    where
+        B: Bandit<Action = A>,
+    {
+        let arms: Vec<A> = bandit.get_actions().iter().cloned().collect();
+        let k = arms.len();
+        Self {
+            arms,
+            sums: vec![0.0; k],
+            counts: vec![0; k],
+            num_pulls: 0,
+        }
+    }
+
+    fn pull<B>(&mut self, bandit: &B, arm: usize)
+    where
+        B: Bandit<Action = A>,
+    {
+        self.sums[arm] += bandit.reward(&self.arms[arm]);
+        self.counts[arm] += 1;
+        self.num_pulls += 1;
+    }
+
+    /// Returns the empirical mean of the given arm,
+    /// or minus infinity if it has never been pulled.
+    fn get_mean(&self, arm: usize) -> f64 {
+        if self.counts[arm] == 0 {
+            return f64::NEG_INFINITY;
+        }
+        self.sums[arm] / f64::from(self.counts[arm])
+    }
+
+    /// Returns the arm with the highest empirical mean among the given ones.
+    fn get_best(&self, arms: &[usize]) -> usize {
+        // unwrap is safe because there is at least one arm
+        *arms
+            .iter()
+            .max_by(|&&i, &&j| self.get_mean(i).total_cmp(&self.get_mean(j)))
+            .unwrap()
+    }
+
+    fn into_result(self, arm: usize) -> (A, u32) {
+        (self.arms[arm].clone(), self.num_pulls)
+    }
+}
+
+/// # Successive Elimination
+///
+/// Fixed-confidence best-arm identification algorithm.
+/// At each round, every remaining arm is pulled once,
+/// and the arms whose upper confidence bound falls below
+/// the highest lower confidence bound are eliminated.
+/// With probability at least `1 - delta`, the returned arm is the best one.
+/// Rewards are assumed to be 1-sub-Gaussian,
+/// for instance bounded in `[0, 1]` or normal with unit variance.
+/// The search is stopped after `max_num_steps` pulls, as given by the config,
+/// even in the middle of a round,
+/// in which case the empirically best remaining arm is returned.
+/// Returns the identified arm and the number of pulls used.
+pub fn successive_elimination<B>(bandit: &B, config: &Config, delta: f64) -> (B::Action, u32)
+where
+    B: Bandit,
+{
+    let mut statistics = ArmStatistics::new(bandit);
+    let k = statistics.arms.len() as f64;
+    let mut active: Vec<usize> = (0..statistics.arms.len()).collect();
+    let mut round = 0;
+    'rounds: while active.len() > 1 {
+        round += 1;
+        for &arm in &active {
+            if statistics.num_pulls >= config.max_num_steps {
+                break 'rounds;
+            }
+            statistics.pull(bandit, arm);
+        }
+        let r = f64::from(round);
+        let radius = (2.0 * (4.0 * k * r * r / delta).ln() / r).sqrt();
+        let best_lower_bound = active
+            .iter()
+            .map(|&arm| statistics.get_mean(arm) - radius)
+            .fold(f64::NEG_INFINITY, f64::max);
+        active.retain(|&arm| statistics.get_mean(arm) + radius >= best_lower_bound);
+    }
+    let best = statistics.get_best(&active);
+    statistics.into_result(best)
+}
+
+/// # LUCB
+///
+/// Fixed-confidence best-arm identification algorithm.
+/// After pulling each arm once, it repeatedly pulls the empirically best arm
+/// and its most ambiguous challenger, the other arm with the highest upper confidence bound,
+/// until the lower confidence bound of the best arm exceeds the upper confidence bound of the challenger.
+/// With probability at least `1 - delta`, the returned arm is the best one.
+/// Rewards are assumed to be 1-sub-Gaussian,
+/// for instance bounded in `[0, 1]` or normal with unit variance.
+/// The search is stopped after `max_num_steps` pulls, as given by the config,
+/// even between the pulls of the best arm and of its challenger,
+/// in which case the empirically best arm is returned.
+/// Returns the identified arm and the number of pulls used.
+pub fn lucb<B>(bandit: &B, config: &Config, delta: f64) -> (B::Action, u32)
+where
+    B: Bandit,
+{
+    let mut statistics = ArmStatistics::new(bandit);
+    let arms: Vec<usize> = (0..statistics.arms.len()).collect();
+    let k = arms.len() as f64;
+    for &arm in arms.iter().take(config.max_num_steps as usize) {
+        statistics.pull(bandit, arm);
+    }
+    loop {
+        let best = statistics.get_best(&arms);
+        if arms.len() == 1 || statistics.num_pulls >= config.max_num_steps {
+            return statistics.into_result(best);
+        }
+        let t = f64::from(statistics.num_pulls);
+        let radius = |count: u32| {
+            (2.0 * (5.0 * k * t.powi(4) / (4.0 * delta)).ln() / f64::from(count)).sqrt()
+        };
+        let upper_bound = |arm: usize| statistics.get_mean(arm) + radius(statistics.counts[arm]);
+        // unwrap is safe because there are at least two arms
+        let challenger = *arms
+            .iter()
+            .filter(|&&arm| arm != best)
+            .max_by(|&&i, &&j| upper_bound(i).total_cmp(&upper_bound(j)))
+            .unwrap();
+        let best_lower_bound = statistics.get_mean(best) - radius(statistics.counts[best]);
+        if best_lower_bound > upper_bound(challenger) {
+            return statistics.into_result(best);
+        }
+        statistics.pull(bandit, best);
+        if statistics.num_pulls < config.max_num_steps {
+            statistics.pull(bandit, challenger);
+        }
+    }
+}
+
+/// # Sequential Halving
+///
+/// Fixed-budget best-arm identification algorithm.
+/// The budget of `max_num_steps` pulls, as given by the config,
+/// is split between `ceil(log2(K))` rounds,
+/// each round getting an even share of what is left of the budget.
+/// At each round, the share is split as evenly as possible between the remaining arms,
+/// and the worse half of them is eliminated.
+/// The budget is never exceeded and is used entirely unless a single arm remains early.
+/// When it is smaller than the number of arms, the arms that could not be pulled are eliminated first.
+/// Returns the identified arm and the number of pulls used.
+pub fn sequential_halving<B>(bandit: &B, config: &Config) -> (B::Action, u32)
+where
+    B: Bandit,
+{
+    let mut statistics = ArmStatistics::new(bandit);
+    let mut active: Vec<usize> = (0..statistics.arms.len()).collect();
+    let num_rounds = active.len().next_power_of_two().trailing_zeros().max(1);
+    for round in 0..num_rounds {
+        if active.len() == 1 {
+            break;
+        }
+        let round_budget = (config.max_num_steps - statistics.num_pulls) / (num_rounds - round);
+        let num_arms = active.len() as u32;
+        for (i, &arm) in active.iter().enumerate() {
+            // the remainder of the share goes to the first arms, one pull each
+            let extra_pull = u32::from((i as u32) < round_budget % num_arms);
+            for _ in 0..round_budget / num_arms + extra_pull {
+                statistics.pull(bandit, arm);
+            }
+        }
+        active.sort_by(|&i, &j| statistics.get_mean(j).total_cmp(&statistics.get_mean(i)));
+        active.truncate(active.len().div_ceil(2));
+    }
+    let best = statistics.get_best(&active);
+    statistics.into_result(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::bandit::{Arm, KArmedBandit};
+
+    fn get_bandit() -> KArmedBandit {
+        KArmedBandit::from_arm_values(vec![0.0, 1.0, 3.0, 0.5])
+    }
+
+    #[test]
+    fn test_successive_elimination() {
+        let config = Config::new().max_num_steps(100_000);
+        let (arm, num_pulls) = successive_elimination(&get_bandit(), &config, 0.05);
+        assert_eq!(arm, Arm(2));
+        assert!(num_pulls < 100_000);
+    }
+
+    #[test]
+    fn test_lucb() {
+        let config = Config::new().max_num_steps(100_000);
+        let (arm, num_pulls) = lucb(&get_bandit(), &config, 0.05);
+        assert_eq!(arm, Arm(2));
+        assert!(num_pulls < 100_000);
+    }
+
+    #[test]
+    fn test_sequential_halving() {
+        let config = Config::new().max_num_steps(400);
+        let (arm, num_pulls) = sequential_halving(&get_bandit(), &config);
+        assert_eq!(arm, Arm(2));
+        assert_eq!(num_pulls, 400);
+    }
+
+    #[test]
+    fn small_budgets() {
+        // the budget runs out in the middle of a round of successive elimination
+        // and between the two pulls of a step of LUCB
+        for budget in 0..12 {
+            let config = Config::new().max_num_steps(budget);
+            let (_, num_pulls) = successive_elimination(&get_bandit(), &config, 0.05);
+            assert!(num_pulls <= budget);
+            let (_, num_pulls) = lucb(&get_bandit(), &config, 0.05);
+            assert!(num_pulls <= budget);
+        }
+    }
+
+    #[test]
+    fn sequential_halving_uses_exact_budget() {
+        // the budget does not divide evenly between the arms and rounds
+        let config = Config::new().max_num_steps(401);
+        let (_, num_pulls) = sequential_halving(&get_bandit(), &config);
+        assert_eq!(num_pulls, 401);
+    }
+
+    #[test]
+    fn sequential_halving_small_budget() {
+        // the budget is smaller than the number of arms times the number of rounds
+        for budget in 0..8 {
+            let config = Config::new().max_num_steps(budget);
+            let (_, num_pulls) = sequential_halving(&get_bandit(), &config);
+            assert_eq!(num_pulls, budget);
+        }
+    }
+
+    #[test]
+    fn single_arm() {
+        let config = Config::new();
+        let bandit = KArmedBandit::from_arm_values(vec![0.0]);
+        assert_eq!(successive_elimination(&bandit, &config, 0.05), (Arm(0), 0));
+        assert_eq!(lucb(&bandit, &config, 0.05), (Arm(0), 1));
+        assert_eq!(sequential_halving(&bandit, &config), (Arm(0), 0));
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_distr::StandardNormal;
+
+use crate::models::{dot, Action, Config, ContextualBandit, Sampler};
+
+use super::argmax;
+
+fn mat_vec(matrix: &[Vec<f64>], vector: &[f64]) -> Vec<f64> {
+    matrix.iter().map(|row| dot(row, vector)).collect()
+}
+
+/// Returns the lower triangular Cholesky factor of a symmetric positive definite matrix.
+fn cholesky(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = matrix.len();
+    let mut lower = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| lower[i][k] * lower[j][k]).sum();
+            if i == j {
+                // clamp to guard against round-off errors
+                lower[i][j] = (matrix[i][i] - sum).max(0.0).sqrt();
+            } else if lower[j][j] > 0.0 {
+                lower[i][j] = (matrix[i][j] - sum) / lower[j][j];
+            }
+        }
+    }
+    lower
+}
+
+/// # Ridge Regression
+///
+/// Online estimator of a linear model `y = theta^T x`
+/// with an L2 regularization of the parameters.
+/// The inverse of the design matrix is maintained incrementally
+/// with the Sherman-Morrison formula, so that no matrix is ever inverted.
+#[derive(Debug, Clone)]
+pub struct RidgeRegression {
+    inverse: Vec<Vec<f64>>,
+    target: Vec<f64>,
+    parameters: Vec<f64>,
+}
+
+impl RidgeRegression {
+    /// Creates a new estimator with the given number of features and regularization.
+    pub fn new(num_features: usize, regularization: f64) -> Self {
+        assert!(regularization > 0.0, "regularization must be positive.");
+        let mut inverse = vec![vec![0.0; num_features]; num_features];
+        for (i, row) in inverse.iter_mut().enumerate() {
+            row[i] = regularization.recip();
+        }
+        Self {
+            inverse,
+            target: vec![0.0; num_features],
+            parameters: vec![0.0; num_features],
+        }
+    }
+
+    /// Returns the estimated parameters.
+    pub fn get_parameters(&self) -> &[f64] {
+        &self.parameters
+    }
+
+    /// Returns the predicted value for the given features.
+    pub fn predict(&self, features: &[f64]) -> f64 {
+        dot(&self.parameters, features)
+    }
+
+    /// Returns `x^T A^-1 x` for the given features,
+    /// which is proportional to the variance of the prediction.
+    pub fn get_variance(&self, features: &[f64]) -> f64 {
+        dot(features, &mat_vec(&self.inverse, features))
+    }
+
+    /// Samples parameters from the normal distribution centered on the estimate
+    /// with covariance `scale^2 A^-1`.
+    pub fn sample_parameters(&self, scale: f64) -> Vec<f64> {
+        let lower = cholesky(&self.inverse);
+        let mut rng = thread_rng();
+        let noise: Vec<f64> = (0..self.parameters.len())
+            .map(|_| rng.sample(StandardNormal))
+            .collect();
+        self.parameters
+            .iter()
+            .zip(mat_vec(&lower, &noise))
+            .map(|(parameter, deviation)| parameter + scale * deviation)
+            .collect()
+    }
+
+    /// Updates the estimate with the given observation.
+    pub fn update(&mut self, features: &[f64], value: f64) {
+        let projected = mat_vec(&self.inverse, features);
+        let denominator = 1.0 + dot(features, &projected);
+        for (i, row) in self.inverse.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry -= projected[i] * projected[j] / denominator;
+            }
+        }
+        for (target, feature) in self.target.iter_mut().zip(features) {
+            *target += value * feature;
+        }
+        self.parameters = mat_vec(&self.inverse, &self.target);
+    }
+}
+
+/// # Contextual Bandit Agent
+///
+/// Represents an algorithm that repeatedly selects an arm given a context
+/// and updates its estimates from the obtained reward.
+pub trait ContextualBanditAgent<A>
+where
+    A: Action,
+{
+    /// Returns the arm to pull in the given context.
+    fn select(&mut self, actions: &Sampler<A>, context: &[f64]) -> A;
+
+    /// Updates the agent with the reward obtained by pulling the given arm in the given context.
+    fn update(&mut self, action: &A, context: &[f64], reward: f64);
+
+    /// Returns the current estimate of the agent for the given arm in the given context.
+    fn predict(&self, action: &A, context: &[f64]) -> f64;
+}
+
+/// # Run Contextual
+///
+/// Plays the given agent against the given contextual bandit
+/// for `max_num_steps` rounds, as given by the config,
+/// and returns the reward obtained at each round.
+pub fn run_contextual<B, G>(bandit: &mut B, agent: &mut G, config: &Config) -> Vec<f64>
+where
+    B: ContextualBandit,
+    G: ContextualBanditAgent<B::Action>,
+{
+    (0..config.max_num_steps)
+        .map(|_| {
+            let context = bandit.next_context();
+            let action = agent.select(bandit.get_actions(), &context);
+            let reward = bandit.reward(&action);
+            agent.update(&action, &context, reward);
+            reward
+        })
+        .collect()
+}
+
+/// Creates one ridge regression estimator per arm.
+fn get_estimators<A>(actions: &Sampler<A>, num_features: usize) -> HashMap<A, RidgeRegression>
+where
+    A: Action,
+{
+    actions
+        .iter()
+        .map(|action| (action.clone(), RidgeRegression::new(num_features, 1.0)))
+        .collect()
+}
+
+/// # LinUCB
+///
+/// Estimates the mean reward of each arm as a linear function of the context
+/// with one ridge regression per arm,
+/// and selects the arm maximizing its prediction plus
+/// the confidence bonus `alpha * sqrt(x^T A^-1 x)`.
+#[derive(Debug, Clone)]
+pub struct LinUCB<A>
+where
+    A: Action,
+{
+    alpha: f64,
+    estimators: HashMap<A, RidgeRegression>,
+}
+
+impl<A> LinUCB<A>
+where
+    A: Action,
+{
+    /// Creates a new LinUCB agent with the given number of features and exploration constant.
+    pub fn new(actions: &Sampler<A>, num_features: usize, alpha: f64) -> Self {
+        Self {
+            alpha,
+            estimators: get_estimators(actions, num_features),
+        }
+    }
+}
+
+impl<A> ContextualBanditAgent<A> for LinUCB<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>, context: &[f64]) -> A {
+        argmax(actions, |action| {
+            let estimator = &self.estimators[action];
+            estimator.predict(context) + self.alpha * estimator.get_variance(context).sqrt()
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, context: &[f64], reward: f64) {
+        self.estimators
+            .get_mut(action)
+            .expect("arm must be one of the actions of the agent.")
+            .update(context, reward);
+    }
+
+    fn predict(&self, action: &A, context: &[f64]) -> f64 {
+        self.estimators[action].predict(context)
+    }
+}
+
+/// # Linear Thompson Sampling
+///
+/// Estimates the mean reward of each arm as a linear function of the context
+/// with one ridge regression per arm.
+/// At each round, parameters are sampled for each arm from a normal distribution
+/// centered on the estimate with covariance `v^2 A^-1`,
+/// and the arm with the highest sampled prediction is selected.
+#[derive(Debug, Clone)]
+pub struct LinearThompsonSampling<A>
+where
+    A: Action,
+{
+    v: f64,
+    estimators: HashMap<A, RidgeRegression>,
+}
+
+impl<A> LinearThompsonSampling<A>
+where
+    A: Action,
+{
+    /// Creates a new linear Thompson sampling agent
+    /// with the given number of features and posterior scale.
+    pub fn new(actions: &Sampler<A>, num_features: usize, v: f64) -> Self {
+        Self {
+            v,
+            estimators: get_estimators(actions, num_features),
+        }
+    }
+}
+
+impl<A> ContextualBanditAgent<A> for LinearThompsonSampling<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>, context: &[f64]) -> A {
+        argmax(actions, |action| {
+            dot(&self.estimators[action].sample_parameters(self.v), context)
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, context: &[f64], reward: f64) {
+        self.estimators
+            .get_mut(action)
+            .expect("arm must be one of the actions of the agent.")
+            .update(context, reward);
+    }
+
+    fn predict(&self, action: &A, context: &[f64]) -> f64 {
+        self.estimators[action].predict(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::bandit::{Arm, LinearContextualBandit};
+
+    fn get_bandit() -> LinearContextualBandit {
+        LinearContextualBandit::from_parameters(
+            vec![
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+            ],
+            0.1,
+        )
+    }
+
+    fn assert_optimal_arms_selected<G>(mut agent: G)
+    where
+        G: ContextualBanditAgent<Arm>,
+    {
+        let mut bandit = get_bandit();
+        let config = Config::new().max_num_steps(500);
+        run_contextual(&mut bandit, &mut agent, &config);
+        let mut num_optimal = 0;
+        for _ in 0..100 {
+            let context = bandit.next_context();
+            let action = agent.select(bandit.get_actions(), &context);
+            let best = argmax(bandit.get_actions(), |arm| bandit.get_mean(arm).unwrap());
+            if &action == best {
+                num_optimal += 1;
+            }
+        }
+        assert!(num_optimal > 85);
+        assert!((agent.predict(&Arm(0), &[1.0, 0.0, 0.0]) - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn ridge_regression_fits_linear_model() {
+        let mut estimator = RidgeRegression::new(2, 1e-3);
+        estimator.update(&[1.0, 0.0], 2.0);
+        estimator.update(&[0.0, 1.0], -1.0);
+        estimator.update(&[1.0, 1.0], 1.0);
+        assert!((estimator.predict(&[1.0, 0.0]) - 2.0).abs() < 1e-2);
+        assert!((estimator.predict(&[0.0, 1.0]) + 1.0).abs() < 1e-2);
+        assert!(estimator.get_variance(&[1.0, 0.0]) < 1.0);
+    }
+
+    #[test]
+    fn test_lin_ucb() {
+        let bandit = get_bandit();
+        assert_optimal_arms_selected(LinUCB::new(bandit.get_actions(), 3, 0.5));
+    }
+
+    #[test]
+    fn test_linear_thompson_sampling() {
+        let bandit = get_bandit();
+        assert_optimal_arms_selected(LinearThompsonSampling::new(bandit.get_actions(), 3, 0.1));
+    }
+}
@@ -0,0 +1,82 @@
+//! # bandit
+//!
+//! The `bandit` module contains the implementations of the multi-armed bandit algorithms.
+//! Each algorithm is a [`BanditAgent`] that selects arms and learns from their rewards.
+//! Agents can be played against any [`Bandit`] with the [`run`] function
+//! and compared over many independent runs with the [`testbed`] function.
+
+mod adversarial;
+pub use adversarial::*;
+
+mod best_arm;
+pub use best_arm::*;
+
+mod contextual;
+pub use contextual::*;
+
+mod nonstationary;
+pub use nonstationary::*;
+
+mod stochastic;
+pub use stochastic::*;
+
+mod testbed;
+pub use testbed::*;
+
+use crate::models::{Action, Bandit, Config, Sampler, StateActionValue};
+
+/// # Bandit Agent
+///
+/// Represents an algorithm that repeatedly selects an arm of a bandit
+/// and updates its estimates from the obtained reward.
+pub trait BanditAgent<A>
+where
+    A: Action,
+{
+    /// Returns the arm to pull next.
+    fn select(&mut self, actions: &Sampler<A>) -> A;
+
+    /// Updates the agent with the reward obtained by pulling the given arm.
+    fn update(&mut self, action: &A, reward: f64);
+
+    /// Returns the current estimates of the agent for each arm.
+    fn get_estimates(&self) -> &StateActionValue<A>;
+}
+
+/// Returns the action with the highest score, breaking ties in order.
+fn argmax<A, F>(actions: &Sampler<A>, mut score: F) -> &A
+where
+    F: FnMut(&A) -> f64,
+{
+    let mut best_action = None;
+    let mut best_score = f64::NEG_INFINITY;
+    for action in actions {
+        let value = score(action);
+        if best_action.is_none() || value > best_score {
+            best_score = value;
+            best_action = Some(action);
+        }
+    }
+    // unwrap is safe because actions is not empty
+    best_action.unwrap()
+}
+
+/// # Run
+///
+/// Plays the given agent against the given bandit
+/// for `max_num_steps` steps, as given by the config,
+/// and returns the reward obtained at each step.
+pub fn run<B, G>(bandit: &B, agent: &mut G, config: &Config) -> Vec<f64>
+where
+    B: Bandit,
+    G: BanditAgent<B::Action>,
+{
+    (0..config.max_num_steps)
+        .map(|_| {
+            let action = agent.select(bandit.get_actions());
+            let reward = bandit.reward(&action);
+            agent.update(&action, reward);
+            reward
+        })
+        .collect()
+}
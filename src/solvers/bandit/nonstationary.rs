@@ -0,0 +1,272 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::models::{Action, Sampler, StateActionValue};
+
+use super::{argmax, BanditAgent};
+
+/// # Constant Step Size
+///
+/// Estimates the value of each arm with an exponential recency-weighted average
+/// of its rewards and selects arms epsilon-greedily with respect to these estimates.
+/// Unlike sample averages, recent rewards weigh more than old ones,
+/// which allows the estimates to track drifting arm values.
+#[derive(Debug, Clone)]
+pub struct ConstantStepSize<A>
+where
+    A: Action,
+{
+    epsilon: f64,
+    step_size: f64,
+    estimates: StateActionValue<A>,
+}
+
+impl<A> ConstantStepSize<A>
+where
+    A: Action,
+{
+    /// Creates a new constant step size agent with the given exploration rate and step size.
+    pub fn new(actions: &Sampler<A>, epsilon: f64, step_size: f64) -> Self {
+        Self {
+            epsilon,
+            step_size,
+            estimates: StateActionValue::new(actions),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for ConstantStepSize<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        self.estimates.epsilon_greedy(actions, self.epsilon).clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        let estimate = self.estimates.get(action);
+        self.estimates
+            .insert(action, estimate + self.step_size * (reward - estimate));
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+/// # Discounted UCB
+///
+/// Selects the arm maximizing its discounted average reward plus
+/// the confidence bonus `c * sqrt(ln(n) / N(a))`,
+/// where `N(a)` is the discounted number of pulls of the arm
+/// and `n` the discounted total number of pulls.
+/// At each step, past rewards and counts are multiplied by the discount factor
+/// so that the agent forgets about old observations.
+#[derive(Debug, Clone)]
+pub struct DiscountedUCB<A>
+where
+    A: Action,
+{
+    discount_factor: f64,
+    c: f64,
+    estimates: StateActionValue<A>,
+    reward_sums: HashMap<A, f64>,
+    counts: HashMap<A, f64>,
+}
+
+impl<A> DiscountedUCB<A>
+where
+    A: Action,
+{
+    /// Creates a new discounted UCB agent with the given discount factor and exploration constant.
+    pub fn new(actions: &Sampler<A>, discount_factor: f64, c: f64) -> Self {
+        Self {
+            discount_factor,
+            c,
+            estimates: StateActionValue::new(actions),
+            reward_sums: HashMap::new(),
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for DiscountedUCB<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        let ln_total = self.counts.values().sum::<f64>().ln();
+        argmax(actions, |action| match self.counts.get(action) {
+            None => f64::INFINITY,
+            Some(&count) => self.estimates.get(action) + self.c * (ln_total / count).sqrt(),
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        for count in self.counts.values_mut() {
+            *count *= self.discount_factor;
+        }
+        for reward_sum in self.reward_sums.values_mut() {
+            *reward_sum *= self.discount_factor;
+        }
+        *self.counts.entry(action.clone()).or_insert(0.0) += 1.0;
+        *self.reward_sums.entry(action.clone()).or_insert(0.0) += reward;
+        for (action, count) in &self.counts {
+            self.estimates
+                .insert(action, self.reward_sums[action] / count);
+        }
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+/// # Sliding Window UCB
+///
+/// Selects the arm maximizing its average reward plus
+/// the confidence bonus `c * sqrt(ln(n) / N(a))`,
+/// where averages and counts only take into account
+/// the last `window` pulls and `n` is the number of pulls in the window.
+/// Arms that have not been pulled within the window are tried first.
+#[derive(Debug, Clone)]
+pub struct SlidingWindowUCB<A>
+where
+    A: Action,
+{
+    window: usize,
+    c: f64,
+    estimates: StateActionValue<A>,
+    history: VecDeque<(A, f64)>,
+    reward_sums: HashMap<A, f64>,
+    counts: HashMap<A, u32>,
+}
+
+impl<A> SlidingWindowUCB<A>
+where
+    A: Action,
+{
+    /// Creates a new sliding window UCB agent with the given window size and exploration constant.
+    pub fn new(actions: &Sampler<A>, window: usize, c: f64) -> Self {
+        assert!(window > 0, "window must contain at least one pull.");
+        Self {
+            window,
+            c,
+            estimates: StateActionValue::new(actions),
+            history: VecDeque::with_capacity(window),
+            reward_sums: HashMap::new(),
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for SlidingWindowUCB<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        let ln_total = (self.history.len() as f64).ln();
+        argmax(actions, |action| match self.counts.get(action) {
+            None | Some(0) => f64::INFINITY,
+            Some(&count) => {
+                self.estimates.get(action) + self.c * (ln_total / f64::from(count)).sqrt()
+            }
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        if self.history.len() == self.window {
+            // unwrap is safe because the window is not empty
+            let (old_action, old_reward) = self.history.pop_front().unwrap();
+            *self.counts.entry(old_action.clone()).or_insert(0) -= 1;
+            *self.reward_sums.entry(old_action).or_insert(0.0) -= old_reward;
+        }
+        self.history.push_back((action.clone(), reward));
+        *self.counts.entry(action.clone()).or_insert(0) += 1;
+        *self.reward_sums.entry(action.clone()).or_insert(0.0) += reward;
+        for (action, &count) in &self.counts {
+            if count > 0 {
+                self.estimates
+                    .insert(action, self.reward_sums[action] / f64::from(count));
+            }
+        }
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::environments::bandit::Arm;
+    use crate::models::{Bandit, Config};
+    use crate::solvers::bandit::run;
+
+    /// A deterministic two-armed bandit whose best arm switches halfway
+    struct SwitchingBandit {
+        actions: Sampler<Arm>,
+        num_steps: Cell<u32>,
+    }
+
+    impl SwitchingBandit {
+        fn new() -> Self {
+            Self {
+                actions: vec![Arm(0), Arm(1)].into(),
+                num_steps: Cell::new(0),
+            }
+        }
+    }
+
+    impl Bandit for SwitchingBandit {
+        type Action = Arm;
+
+        fn get_actions(&self) -> &Sampler<Self::Action> {
+            &self.actions
+        }
+
+        fn reward(&self, action: &Self::Action) -> f64 {
+            self.num_steps.set(self.num_steps.get() + 1);
+            let best = if self.num_steps.get() <= 500 { 0 } else { 1 };
+            if action.0 == best {
+                1.0
+            } else {
+                0.0
+            }
+        }
+    }
+
+    fn assert_switch_tracked<G>(mut agent: G)
+    where
+        G: BanditAgent<Arm>,
+    {
+        let bandit = SwitchingBandit::new();
+        let config = Config::new().max_num_steps(1_000);
+        let rewards = run(&bandit, &mut agent, &config);
+        assert_eq!(agent.get_estimates().greedy(), &Arm(1));
+        let late_average: f64 = rewards[900..].iter().sum::<f64>() / 100.0;
+        assert!(late_average > 0.8);
+    }
+
+    #[test]
+    fn test_constant_step_size() {
+        let bandit = SwitchingBandit::new();
+        assert_switch_tracked(ConstantStepSize::new(bandit.get_actions(), 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_discounted_ucb() {
+        let bandit = SwitchingBandit::new();
+        assert_switch_tracked(DiscountedUCB::new(bandit.get_actions(), 0.95, 0.5));
+    }
+
+    #[test]
+    fn test_sliding_window_ucb() {
+        let bandit = SwitchingBandit::new();
+        assert_switch_tracked(SlidingWindowUCB::new(bandit.get_actions(), 50, 0.5));
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_distr::{Beta, Normal};
+
+use crate::models::{Action, Sampler, StateActionValue};
+
+use super::{argmax, BanditAgent};
+
+/// # Sample Average
+///
+/// Estimates the value of each arm with the average of its rewards
+/// and selects arms epsilon-greedily with respect to these estimates.
+#[derive(Debug, Clone)]
+pub struct SampleAverage<A>
+where
+    A: Action,
+{
+    epsilon: f64,
+    estimates: StateActionValue<A>,
+    counts: HashMap<A, u32>,
+}
+
+impl<A> SampleAverage<A>
+where
+    A: Action,
+{
+    /// Creates a new sample-average agent with the given exploration rate.
+    pub fn new(actions: &Sampler<A>, epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            estimates: StateActionValue::new(actions),
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for SampleAverage<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        self.estimates.epsilon_greedy(actions, self.epsilon).clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        let count = self.counts.entry(action.clone()).or_insert(0);
+        *count += 1;
+        let estimate = self.estimates.get(action);
+        self.estimates
+            .insert(action, estimate + (reward - estimate) / f64::from(*count));
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+/// # UCB1
+///
+/// Selects the arm maximizing its average reward plus
+/// the confidence bonus `c * sqrt(ln(t) / N(a))`.
+/// Each arm is pulled once before the bonus is used.
+/// The original UCB1 algorithm uses `c = sqrt(2)`.
+#[derive(Debug, Clone)]
+pub struct UCB1<A>
+where
+    A: Action,
+{
+    c: f64,
+    estimates: StateActionValue<A>,
+    counts: HashMap<A, u32>,
+    num_steps: u32,
+}
+
+impl<A> UCB1<A>
+where
+    A: Action,
+{
+    /// Creates a new UCB1 agent with the given exploration constant.
+    pub fn new(actions: &Sampler<A>, c: f64) -> Self {
+        Self {
+            c,
+            estimates: StateActionValue::new(actions),
+            counts: HashMap::new(),
+            num_steps: 0,
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for UCB1<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        let ln_steps = f64::from(self.num_steps).ln();
+        argmax(actions, |action| match self.counts.get(action) {
+            None => f64::INFINITY,
+            Some(&count) => {
+                self.estimates.get(action) + self.c * (ln_steps / f64::from(count)).sqrt()
+            }
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        self.num_steps += 1;
+        let count = self.counts.entry(action.clone()).or_insert(0);
+        *count += 1;
+        let estimate = self.estimates.get(action);
+        self.estimates
+            .insert(action, estimate + (reward - estimate) / f64::from(*count));
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+/// # Gradient Bandit
+///
+/// Learns a numerical preference for each arm
+/// and samples arms from the softmax of the preferences.
+/// The preferences are updated by stochastic gradient ascent on the expected reward.
+/// If enabled, the average reward is used as a baseline to reduce the variance of the updates.
+#[derive(Debug, Clone)]
+pub struct GradientBandit<A>
+where
+    A: Action,
+{
+    step_size: f64,
+    use_baseline: bool,
+    preferences: StateActionValue<A>,
+    baseline: f64,
+    num_steps: u32,
+}
+
+impl<A> GradientBandit<A>
+where
+    A: Action,
+{
+    /// Creates a new gradient bandit agent with the given step size.
+    pub fn new(actions: &Sampler<A>, step_size: f64, use_baseline: bool) -> Self {
+        Self {
+            step_size,
+            use_baseline,
+            preferences: StateActionValue::new(actions),
+            baseline: 0.0,
+            num_steps: 0,
+        }
+    }
+
+    /// Returns the probability of selecting each arm.
+    pub fn get_probabilities(&self) -> HashMap<A, f64> {
+        self.preferences.softmax_probabilities(1.0)
+    }
+}
+
+impl<A> BanditAgent<A> for GradientBandit<A>
+where
+    A: Action,
+{
+    fn select(&mut self, _actions: &Sampler<A>) -> A {
+        self.preferences.softmax(1.0).clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        self.num_steps += 1;
+        if self.use_baseline {
+            self.baseline += (reward - self.baseline) / f64::from(self.num_steps);
+        }
+        let advantage = reward - self.baseline;
+        for (other, probability) in self.get_probabilities() {
+            let indicator = if &other == action { 1.0 } else { 0.0 };
+            let preference = self.preferences.get(&other);
+            self.preferences.insert(
+                &other,
+                preference + self.step_size * advantage * (indicator - probability),
+            );
+        }
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.preferences
+    }
+}
+
+/// # Gaussian Thompson Sampling
+///
+/// Maintains a normal posterior over the mean reward of each arm,
+/// using the normal conjugate prior for rewards with a known variance.
+/// By default, the prior is a standard normal distribution and the rewards have unit variance.
+/// At each step, a mean is sampled from each posterior
+/// and the arm with the highest sample is selected.
+#[derive(Debug, Clone)]
+pub struct GaussianThompsonSampling<A>
+where
+    A: Action,
+{
+    noise_precision: f64,
+    means: StateActionValue<A>,
+    precisions: StateActionValue<A>,
+}
+
+impl<A> GaussianThompsonSampling<A>
+where
+    A: Action,
+{
+    /// Creates a new Thompson sampling agent with a standard normal prior.
+    pub fn new(actions: &Sampler<A>) -> Self {
+        Self {
+            noise_precision: 1.0,
+            means: StateActionValue::new(actions),
+            precisions: StateActionValue::with_value(actions, 1.0),
+        }
+    }
+
+    /// Sets the mean and variance of the prior of every arm and returns the agent.
+    pub fn prior(mut self, mean: f64, variance: f64) -> Self {
+        assert!(variance > 0.0, "prior variance must be positive.");
+        let arms: Vec<A> = self.means.iter().map(|(arm, _)| arm.clone()).collect();
+        for arm in &arms {
+            self.means.insert(arm, mean);
+            self.precisions.insert(arm, variance.recip());
+        }
+        self
+    }
+
+    /// Sets the known variance of the rewards and returns the agent.
+    pub fn noise_variance(mut self, variance: f64) -> Self {
+        assert!(variance > 0.0, "noise variance must be positive.");
+        self.noise_precision = variance.recip();
+        self
+    }
+}
+
+impl<A> BanditAgent<A> for GaussianThompsonSampling<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        let mut rng = thread_rng();
+        argmax(actions, |action| {
+            let standard_deviation = self.precisions.get(action).recip().sqrt();
+            // unwrap is safe because the standard deviation is positive
+            Normal::new(self.means.get(action), standard_deviation)
+                .unwrap()
+                .sample(&mut rng)
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        let precision = self.precisions.get(action);
+        let mean = self.means.get(action);
+        let new_precision = precision + self.noise_precision;
+        self.precisions.insert(action, new_precision);
+        self.means.insert(
+            action,
+            (precision * mean + self.noise_precision * reward) / new_precision,
+        );
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.means
+    }
+}
+
+/// # Beta Thompson Sampling
+///
+/// Maintains a beta posterior over the success probability of each arm,
+/// using the beta conjugate prior for Bernoulli rewards.
+/// The prior is uniform, that is `Beta(1, 1)`.
+/// Rewards must lie between zero and one:
+/// rewards that are not binary are treated as a success with probability equal to the reward.
+/// The estimates are the means of the posteriors.
+#[derive(Debug, Clone)]
+pub struct BetaThompsonSampling<A>
+where
+    A: Action,
+{
+    successes: StateActionValue<A>,
+    failures: StateActionValue<A>,
+    estimates: StateActionValue<A>,
+}
+
+impl<A> BetaThompsonSampling<A>
+where
+    A: Action,
+{
+    /// Creates a new Thompson sampling agent with a uniform prior.
+    pub fn new(actions: &Sampler<A>) -> Self {
+        Self {
+            successes: StateActionValue::with_value(actions, 1.0),
+            failures: StateActionValue::with_value(actions, 1.0),
+            estimates: StateActionValue::with_value(actions, 0.5),
+        }
+    }
+}
+
+impl<A> BanditAgent<A> for BetaThompsonSampling<A>
+where
+    A: Action,
+{
+    fn select(&mut self, actions: &Sampler<A>) -> A {
+        let mut rng = thread_rng();
+        argmax(actions, |action| {
+            // unwrap is safe because the parameters are positive
+            Beta::new(self.successes.get(action), self.failures.get(action))
+                .unwrap()
+                .sample(&mut rng)
+        })
+        .clone()
+    }
+
+    fn update(&mut self, action: &A, reward: f64) {
+        assert!(
+            (0.0..=1.0).contains(&reward),
+            "rewards must lie between zero and one for beta Thompson sampling."
+        );
+        let counts = if random::<f64>() < reward {
+            &mut self.successes
+        } else {
+            &mut self.failures
+        };
+        counts.insert(action, counts.get(action) + 1.0);
+        let successes = self.successes.get(action);
+        let failures = self.failures.get(action);
+        self.estimates
+            .insert(action, successes / (successes + failures));
+    }
+
+    fn get_estimates(&self) -> &StateActionValue<A> {
+        &self.estimates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::bandit::{Arm, ArmDistribution, DistributionBandit, KArmedBandit};
+    use crate::models::{Bandit, Config};
+    use crate::solvers::bandit::run;
+
+    fn assert_best_arm_found<G>(mut agent: G)
+    where
+        G: BanditAgent<Arm>,
+    {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 5.0, 1.0]);
+        let config = Config::new().max_num_steps(1_000);
+        let rewards = run(&bandit, &mut agent, &config);
+        assert_eq!(rewards.len(), 1_000);
+        assert_eq!(agent.get_estimates().greedy(), &Arm(1));
+        let average: f64 = rewards.iter().sum::<f64>() / rewards.len() as f64;
+        assert!(average > 3.5);
+    }
+
+    #[test]
+    fn test_sample_average() {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 5.0, 1.0]);
+        assert_best_arm_found(SampleAverage::new(bandit.get_actions(), 0.1));
+    }
+
+    #[test]
+    fn test_ucb1() {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 5.0, 1.0]);
+        assert_best_arm_found(UCB1::new(bandit.get_actions(), 2.0_f64.sqrt()));
+    }
+
+    #[test]
+    fn test_gradient_bandit() {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 5.0, 1.0]);
+        assert_best_arm_found(GradientBandit::new(bandit.get_actions(), 0.1, true));
+    }
+
+    #[test]
+    fn test_gaussian_thompson_sampling() {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 5.0, 1.0]);
+        assert_best_arm_found(GaussianThompsonSampling::new(bandit.get_actions()));
+    }
+
+    #[test]
+    fn test_gaussian_thompson_sampling_with_known_variance() {
+        let bandit = DistributionBandit::new(vec![
+            ArmDistribution::Gaussian(0.0, 3.0),
+            ArmDistribution::Gaussian(5.0, 3.0),
+        ]);
+        let mut agent = GaussianThompsonSampling::new(bandit.get_actions())
+            .prior(0.0, 10.0)
+            .noise_variance(9.0);
+        let config = Config::new().max_num_steps(1_000);
+        run(&bandit, &mut agent, &config);
+        assert_eq!(agent.get_estimates().greedy(), &Arm(1));
+    }
+
+    #[test]
+    fn test_beta_thompson_sampling() {
+        let bandit = DistributionBandit::bernoulli(vec![0.2, 0.8, 0.5]);
+        let mut agent = BetaThompsonSampling::new(bandit.get_actions());
+        let config = Config::new().max_num_steps(1_000);
+        let rewards = run(&bandit, &mut agent, &config);
+        assert_eq!(agent.get_estimates().greedy(), &Arm(1));
+        let average: f64 = rewards.iter().sum::<f64>() / rewards.len() as f64;
+        assert!(average > 0.7);
+    }
+
+    #[test]
+    fn gradient_bandit_probabilities_sum_to_one() {
+        let bandit = KArmedBandit::from_arm_values(vec![0.0, 5.0, 1.0]);
+        let mut agent = GradientBandit::new(bandit.get_actions(), 0.1, false);
+        agent.update(&Arm(1), 1.0);
+        let total: f64 = agent.get_probabilities().values().sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+}
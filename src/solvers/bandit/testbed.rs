@@ -0,0 +1,147 @@
+use crate::environments::bandit::{Arm, KArmedBandit};
+use crate::models::{Bandit, Config};
+
+use super::BanditAgent;
+
+/// # Testbed Results
+///
+/// Contains the curves of a bandit testbed, averaged over all runs.
+/// Each curve has one value per step.
+/// The optimal action rates and cumulative regrets are only available
+/// when the bandits expose their true arm means with [`Bandit::get_mean`].
+#[derive(Debug, Clone)]
+pub struct TestbedResults {
+    pub average_rewards: Vec<f64>,
+    pub optimal_action_rates: Option<Vec<f64>>,
+    pub cumulative_regrets: Option<Vec<f64>>,
+}
+
+/// Returns the highest true arm mean of the bandit if all means are known.
+fn get_best_mean<B>(bandit: &B) -> Option<f64>
+where
+    B: Bandit,
+{
+    bandit
+        .get_actions()
+        .iter()
+        .map(|action| bandit.get_mean(action))
+        .try_fold(f64::NEG_INFINITY, |best, mean| {
+            mean.map(|mean| best.max(mean))
+        })
+}
+
+/// # Testbed
+///
+/// Executes `num_runs` independent runs of a bandit algorithm
+/// for `max_num_steps` steps each, as given by the config,
+/// and returns the curves averaged over the runs.
+/// Each run plays a fresh agent, built by `make_agent`,
+/// against a fresh bandit, built by `make_bandit`.
+/// The regret at each step is the difference between the best arm mean
+/// and the mean of the selected arm, both taken at the time of the selection
+/// so that the regret of non-stationary bandits is measured against the current best arm.
+pub fn testbed<B, G, FB, FG>(
+    num_runs: u32,
+    config: &Config,
+    make_bandit: FB,
+    make_agent: FG,
+) -> TestbedResults
+where
+    B: Bandit,
+    G: BanditAgent<B::Action>,
+    FB: Fn() -> B,
+    FG: Fn(&B) -> G,
+{
+    assert!(num_runs > 0, "testbed must execute at least one run.");
+    let num_steps = config.max_num_steps as usize;
+    let mut reward_sums = vec![0.0; num_steps];
+    let mut optimal_action_counts = Some(vec![0.0; num_steps]);
+    let mut regret_sums = Some(vec![0.0; num_steps]);
+    for _ in 0..num_runs {
+        let bandit = make_bandit();
+        let mut agent = make_agent(&bandit);
+        let mut cumulative_regret = 0.0;
+        for step in 0..num_steps {
+            let best_mean = get_best_mean(&bandit);
+            if best_mean.is_none() {
+                optimal_action_counts = None;
+                regret_sums = None;
+            }
+            let action = agent.select(bandit.get_actions());
+            let mean = bandit.get_mean(&action);
+            let reward = bandit.reward(&action);
+            agent.update(&action, reward);
+            reward_sums[step] += reward;
+            if let (Some(best_mean), Some(mean)) = (best_mean, mean) {
+                cumulative_regret += best_mean - mean;
+                if let Some(counts) = optimal_action_counts.as_mut() {
+                    counts[step] += if mean == best_mean { 1.0 } else { 0.0 };
+                }
+                if let Some(sums) = regret_sums.as_mut() {
+                    sums[step] += cumulative_regret;
+                }
+            }
+        }
+    }
+    let average = |sums: Vec<f64>| -> Vec<f64> {
+        sums.into_iter()
+            .map(|sum| sum / f64::from(num_runs))
+            .collect()
+    };
+    TestbedResults {
+        average_rewards: average(reward_sums),
+        optimal_action_rates: optimal_action_counts.map(average),
+        cumulative_regrets: regret_sums.map(average),
+    }
+}
+
+/// # K-Armed Testbed
+///
+/// Executes the classic k-armed testbed:
+/// each run plays a fresh agent against a new [`KArmedBandit`]
+/// whose arm values are drawn from a standard normal distribution.
+pub fn k_armed_testbed<G, FG>(
+    k: i32,
+    num_runs: u32,
+    config: &Config,
+    make_agent: FG,
+) -> TestbedResults
+where
+    G: BanditAgent<Arm>,
+    FG: Fn(&KArmedBandit) -> G,
+{
+    testbed(num_runs, config, || KArmedBandit::new(k), make_agent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::bandit::{SampleAverage, UCB1};
+
+    #[test]
+    fn test_k_armed_testbed() {
+        let config = Config::new().max_num_steps(200);
+        let results = k_armed_testbed(10, 50, &config, |bandit| {
+            UCB1::new(bandit.get_actions(), 2.0)
+        });
+        assert_eq!(results.average_rewards.len(), 200);
+        let rates = results.optimal_action_rates.unwrap();
+        assert!(rates[199] > rates[0]);
+        let regrets = results.cumulative_regrets.unwrap();
+        assert!(regrets.windows(2).all(|pair| pair[1] >= pair[0]));
+    }
+
+    #[test]
+    fn test_testbed_random_regret() {
+        let config = Config::new().max_num_steps(100);
+        let results = testbed(
+            10,
+            &config,
+            || KArmedBandit::from_arm_values(vec![1.0, 0.0]),
+            |bandit| SampleAverage::new(bandit.get_actions(), 1.0),
+        );
+        let regrets = results.cumulative_regrets.unwrap();
+        // a uniformly random agent has an expected regret of 0.5 per step
+        assert!((regrets[99] - 50.0).abs() < 15.0);
+    }
+}
@@ -0,0 +1,148 @@
+//! # batch
+//!
+//! The `batch` module contains the implementations of the offline batch learning algorithms.
+//! They learn from a fixed dataset of transitions
+//! without ever interacting with the environment or calling [`MDP::transition`](crate::models::MDP::transition).
+
+use std::collections::HashMap;
+
+use crate::models::{Action, ActionValue, Config, Sampler, State, Transition};
+
+use super::temporal_difference::q_learning_update;
+
+/// # Fitted Q Iteration
+///
+/// This function implements the tabular fitted Q iteration algorithm.
+/// At each iteration, the action value of every state-action pair in the dataset
+/// is replaced by the average of its Bellman targets computed with the previous action value.
+/// State-action pairs absent from the dataset keep their initial value of zero.
+/// The algorithm stops when no value changes by more than the `tolerance` parameter
+/// or after `max_num_iterations` iterations.
+pub fn fitted_q_iteration<S, A>(
+    states: &Sampler<S>,
+    actions: &Sampler<A>,
+    config: &Config,
+    transitions: &[Transition<S, A>],
+) -> ActionValue<S, A>
+where
+    S: State,
+    A: Action,
+{
+    let mut samples: HashMap<(&S, &A), Vec<&Transition<S, A>>> = HashMap::new();
+    for transition in transitions {
+        samples
+            .entry((&transition.state, &transition.action))
+            .or_default()
+            .push(transition);
+    }
+    let mut action_value = ActionValue::new(states, actions);
+    for _ in 0..config.max_num_iterations {
+        let mut new_action_value = action_value.clone();
+        let mut delta: f64 = 0.0;
+        for ((state, action), transitions) in &samples {
+            let total: f64 = transitions
+                .iter()
+                .map(|transition| {
+                    let q_value = if transition.done {
+                        0.0
+                    } else {
+                        let next_state = &transition.next_state;
+                        action_value.get(next_state, action_value.greedy(next_state))
+                    };
+                    transition.reward + config.discount_factor * q_value
+                })
+                .sum();
+            let target = total / transitions.len() as f64;
+            delta = delta.max((target - action_value.get(state, action)).abs());
+            new_action_value.insert(state, action, target);
+        }
+        action_value = new_action_value;
+        if delta < config.tolerance {
+            break;
+        }
+    }
+    action_value
+}
+
+/// # Batch Q-Learning
+///
+/// This function implements batch temporal difference learning with the Q-Learning update rule.
+/// It sweeps repeatedly over the dataset, applying the update to each transition,
+/// until no value changes by more than the `tolerance` parameter during a sweep
+/// or after `max_num_iterations` sweeps.
+/// The step size of the updates is given by the `learning_rate` parameter.
+pub fn batch_q_learning<S, A>(
+    states: &Sampler<S>,
+    actions: &Sampler<A>,
+    config: &Config,
+    transitions: &[Transition<S, A>],
+) -> ActionValue<S, A>
+where
+    S: State,
+    A: Action,
+{
+    let mut action_value = ActionValue::new(states, actions);
+    for _ in 0..config.max_num_iterations {
+        let mut delta: f64 = 0.0;
+        for transition in transitions {
+            let td_error = q_learning_update(&mut action_value, config, transition);
+            delta = delta.max((config.learning_rate * td_error).abs());
+        }
+        if delta < config.tolerance {
+            break;
+        }
+    }
+    action_value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        assert_policy_optimal, get_gridworld, get_test_config, Gridworld, GridworldAction,
+        GridworldState,
+    };
+    use crate::models::MDP;
+
+    fn get_dataset(mdp: &Gridworld) -> Vec<Transition<GridworldState, GridworldAction>> {
+        let mut transitions = Vec::new();
+        for state in mdp.get_states() {
+            if mdp.is_state_terminal(state) {
+                continue;
+            }
+            for action in mdp.get_actions() {
+                let (next_state, reward) = mdp.transition(state, action);
+                transitions.push(Transition {
+                    state: state.clone(),
+                    action: action.clone(),
+                    reward,
+                    done: mdp.is_state_terminal(&next_state),
+                    next_state,
+                });
+            }
+        }
+        transitions
+    }
+
+    #[test]
+    fn test_fitted_q_iteration() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let transitions = get_dataset(&mdp);
+        let action_value =
+            fitted_q_iteration(mdp.get_states(), mdp.get_actions(), &config, &transitions);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_batch_q_learning() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let transitions = get_dataset(&mdp);
+        let action_value =
+            batch_q_learning(mdp.get_states(), mdp.get_actions(), &config, &transitions);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+}
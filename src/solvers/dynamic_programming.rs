@@ -2,15 +2,19 @@
 //!
 //! The `dynamic_programming` module contains the implementations of the dynamic programming algorithms.
 
-use crate::models::{Config, Policy, StateValue, MDP};
+use crate::models::{ActionValue, Config, Policy, PolicyAndValue, StateValue, MDP};
 
 /// # Policy Evaluation
 ///
 /// This function implements the policy evaluation algorithm.
 /// It works by using the Bellman equation to iteratively update the state value.
-/// The algorithm stops when the state value converge.
+/// The algorithm stops when the state value converge,
+/// that is when no value changes by more than the `tolerance` parameter.
 /// If the `iterations_before_improvement` parameter is set,
 /// the algorithm will stop early after the given number of iterations.
+/// With a discount factor of one, the values of an improper policy,
+/// which does not reach a terminal state, may never converge:
+/// see the [`stochastic_shortest_path`](crate::solvers::stochastic_shortest_path) module instead.
 pub fn policy_evaluation<M>(
     mdp: &M,
     config: &Config,
@@ -34,7 +38,7 @@ where
             delta = delta.max((new_state_value - state_value.get(state)).abs());
             state_value.insert(state, new_state_value);
         }
-        if delta < 1e-5
+        if delta < config.tolerance
             || config
                 .iterations_before_improvement
                 .is_some_and(|n| iteration >= n)
@@ -77,7 +81,9 @@ where
     policy
 }
 
-fn policy_value_iteration<M>(mdp: &M, config: &Config) -> StateValue<M::State>
+/// Alternates policy evaluation and policy improvement until the policy converges,
+/// with the evaluation stopped early if the `iterations_before_improvement` parameter is set.
+pub(crate) fn policy_value_iteration<M>(mdp: &M, config: &Config) -> StateValue<M::State>
 where
     M: MDP,
 {
@@ -131,6 +137,79 @@ where
     policy_value_iteration(mdp, config)
 }
 
+/// Returns the soft action value of the given state value,
+/// where the value of each state-action pair is the reward
+/// plus the discounted value of the next state.
+/// Terminal states have a value of zero.
+fn get_soft_action_value<M>(
+    mdp: &M,
+    config: &Config,
+    state_value: &StateValue<M::State>,
+) -> ActionValue<M::State, M::Action>
+where
+    M: MDP,
+{
+    let states = mdp.get_states();
+    let actions = mdp.get_actions();
+    let mut action_value = ActionValue::new(states, actions);
+    for state in states {
+        for action in actions {
+            let (next_state, reward) = mdp.transition(state, action);
+            let next_value = if mdp.is_state_terminal(&next_state) {
+                0.0
+            } else {
+                state_value.get(&next_state)
+            };
+            action_value.insert(state, action, reward + config.discount_factor * next_value);
+        }
+    }
+    action_value
+}
+
+/// # Soft Value Iteration
+///
+/// This function implements the soft value iteration algorithm,
+/// which solves the entropy-regularized, or maximum entropy, MDP.
+/// It works like value iteration, except that the maximum over actions
+/// in the Bellman backup is replaced by the log-sum-exp
+/// with the `temperature` parameter in the config:
+/// `V(s) = temperature * ln(sum(exp(Q(s, a) / temperature)))`.
+/// The higher the temperature, the more the entropy of the policy is rewarded,
+/// and as the temperature goes to zero, the algorithm approaches value iteration.
+/// Terminal states have a value of zero.
+/// The algorithm stops when no value changes by more than the `tolerance` parameter
+/// or after the `max_num_iterations` parameter in the config.
+/// Returns the Boltzmann policy of the soft action values, with the same temperature,
+/// along with the soft state value.
+pub fn soft_value_iteration<M>(mdp: &M, config: &Config) -> PolicyAndValue<M::State, M::Action>
+where
+    M: MDP,
+{
+    let states = mdp.get_states();
+    let mut state_value = StateValue::new(states);
+    for _ in 0..config.max_num_iterations {
+        let action_value = get_soft_action_value(mdp, config, &state_value);
+        let mut delta: f64 = 0.0;
+        for state in states {
+            let new_state_value = if mdp.is_state_terminal(state) {
+                0.0
+            } else {
+                action_value
+                    .get_state_action_value(state)
+                    .log_sum_exp(config.temperature)
+            };
+            delta = delta.max((new_state_value - state_value.get(state)).abs());
+            state_value.insert(state, new_state_value);
+        }
+        if delta < config.tolerance {
+            break;
+        }
+    }
+    let action_value = get_soft_action_value(mdp, config, &state_value);
+    let policy = action_value.boltzmann_policy(states, mdp.get_actions(), config.temperature);
+    (policy, state_value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,4 +255,29 @@ mod tests {
         assert_state_value_correct(&state_value);
         assert_policy_optimal(&policy);
     }
+
+    #[test]
+    fn test_soft_value_iteration_low_temperature() {
+        let mdp = get_gridworld();
+        let config = get_test_config().temperature(1e-3);
+        let (policy, state_value) = soft_value_iteration(&mdp, &config);
+        let expected = get_test_state_value(mdp.get_states());
+        for state in mdp.get_states() {
+            assert!((state_value.get(state) - expected.get(state)).abs() < 1e-2);
+        }
+        assert_policy_optimal(&policy.most_likely_policy(mdp.get_states(), mdp.get_actions()));
+    }
+
+    #[test]
+    fn test_soft_value_iteration_rewards_entropy() {
+        let mdp = get_gridworld();
+        let config = get_test_config().temperature(10.0);
+        let (_, state_value) = soft_value_iteration(&mdp, &config);
+        let expected = get_test_state_value(mdp.get_states());
+        for state in mdp.get_states() {
+            if !mdp.is_state_terminal(state) {
+                assert!(state_value.get(state) > expected.get(state));
+            }
+        }
+    }
 }
@@ -0,0 +1,293 @@
+//! # gradient_temporal_difference
+//!
+//! The `gradient_temporal_difference` module contains the implementations
+//! of the gradient temporal difference algorithms with linear function approximation.
+//! Unlike semi-gradient methods, they perform a true stochastic gradient descent
+//! on the projected Bellman error, which makes them stable off-policy.
+//! Besides the primary weights of the value,
+//! they learn secondary weights estimating the expected temporal difference error
+//! given the features, whose learning rate is the `secondary_learning_rate` parameter in the config.
+//! The solvers only interact with the problem through the [`Environment`] trait.
+
+use crate::models::{
+    Config, Environment, Exploration, FeatureVector, LinearActionValue, LinearStateValue,
+    StateActionFeatures, StateFeatures, StochasticPolicy,
+};
+
+/// Updates the secondary weights towards the temporal difference error
+/// and returns their prediction for the given features before the update.
+fn update_secondary_weights(
+    secondary_weights: &mut [f64],
+    config: &Config,
+    features: &FeatureVector,
+    td_error: f64,
+    ratio: f64,
+) -> f64 {
+    let prediction = features.dot(secondary_weights);
+    features.add_scaled_to(
+        secondary_weights,
+        config.secondary_learning_rate * ratio * (td_error - prediction),
+    );
+    prediction
+}
+
+fn gtd2_tdc<E, F>(
+    env: &mut E,
+    config: &Config,
+    mut state_value: LinearStateValue<E::State, F>,
+    target: &StochasticPolicy<E::State, E::Action>,
+    behaviour: &StochasticPolicy<E::State, E::Action>,
+    tdc: bool,
+) -> LinearStateValue<E::State, F>
+where
+    E: Environment,
+    F: StateFeatures<E::State>,
+{
+    let mut secondary_weights = vec![0.0; state_value.get_weights().len()];
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = behaviour.sample(&state).clone();
+            let (next_state, reward, done, truncated) = env.step(&action);
+            let ratio = target.get(&state, &action) / behaviour.get(&state, &action);
+            let features = state_value.get_features().get_features(&state);
+            let next_features = if done {
+                FeatureVector::Sparse(vec![])
+            } else {
+                state_value.get_features().get_features(&next_state)
+            };
+            let weights = state_value.get_weights_mut();
+            let td_error = reward + config.discount_factor * next_features.dot(weights)
+                - features.dot(weights);
+            let prediction = update_secondary_weights(
+                &mut secondary_weights,
+                config,
+                &features,
+                td_error,
+                ratio,
+            );
+            // update primary weights
+            let step = config.learning_rate * ratio;
+            if tdc {
+                features.add_scaled_to(weights, step * td_error);
+            } else {
+                features.add_scaled_to(weights, step * prediction);
+            }
+            next_features.add_scaled_to(weights, -step * config.discount_factor * prediction);
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    state_value
+}
+
+/// # GTD2
+///
+/// This function implements the GTD2 algorithm
+/// to evaluate the target policy while following the behaviour policy.
+/// At each step, the primary weights are updated with
+/// `w += alpha * rho * (x - gamma * x') * (x^T v)`
+/// where `rho` is the importance sampling ratio of the action taken,
+/// `x` and `x'` the features of the current and next states
+/// and `v` the secondary weights.
+/// The learning starts from the given linear state value.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+pub fn gtd2<E, F>(
+    env: &mut E,
+    config: &Config,
+    state_value: LinearStateValue<E::State, F>,
+    target: &StochasticPolicy<E::State, E::Action>,
+    behaviour: &StochasticPolicy<E::State, E::Action>,
+) -> LinearStateValue<E::State, F>
+where
+    E: Environment,
+    F: StateFeatures<E::State>,
+{
+    gtd2_tdc(env, config, state_value, target, behaviour, false)
+}
+
+/// # TDC
+///
+/// This function implements the TD with gradient correction algorithm,
+/// also known as GTD(0),
+/// to evaluate the target policy while following the behaviour policy.
+/// At each step, the primary weights are updated with
+/// `w += alpha * rho * (delta * x - gamma * x' * (x^T v))`
+/// where `rho` is the importance sampling ratio of the action taken,
+/// `delta` the temporal difference error,
+/// `x` and `x'` the features of the current and next states
+/// and `v` the secondary weights.
+/// It is the semi-gradient TD(0) update plus a correction term,
+/// and it usually learns faster than [`gtd2`].
+/// The learning starts from the given linear state value.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+pub fn tdc<E, F>(
+    env: &mut E,
+    config: &Config,
+    state_value: LinearStateValue<E::State, F>,
+    target: &StochasticPolicy<E::State, E::Action>,
+    behaviour: &StochasticPolicy<E::State, E::Action>,
+) -> LinearStateValue<E::State, F>
+where
+    E: Environment,
+    F: StateFeatures<E::State>,
+{
+    gtd2_tdc(env, config, state_value, target, behaviour, true)
+}
+
+/// # Greedy-GQ
+///
+/// This function implements the Greedy-GQ algorithm,
+/// a gradient temporal difference control algorithm
+/// which learns the action value of the greedy policy while following the exploration strategy.
+/// At each step, the primary weights are updated with
+/// `w += alpha * (delta * x - gamma * x' * (x^T v))`
+/// where `delta` is the Q-Learning temporal difference error,
+/// `x` the features of the current state-action pair,
+/// `x'` the features of the next state and its greedy action
+/// and `v` the secondary weights.
+/// The learning starts from the given linear action value.
+/// The algorithm uses the given exploration strategy to select actions,
+/// from the approximate action values of the current state.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+pub fn greedy_gq<E, X, F>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+    mut action_value: LinearActionValue<E::State, E::Action, F>,
+) -> LinearActionValue<E::State, E::Action, F>
+where
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
+    F: StateActionFeatures<E::State, E::Action>,
+{
+    let mut secondary_weights = vec![0.0; action_value.get_weights().len()];
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = exploration.choose(
+                &state,
+                &action_value.get_state_action_value(&state, env.get_actions()),
+                env.get_actions(),
+            );
+            let (next_state, reward, done, truncated) = env.step(&action);
+            let features = action_value.get_features().get_features(&state, &action);
+            let next_features = if done {
+                FeatureVector::Sparse(vec![])
+            } else {
+                let greedy_action = action_value.greedy(&next_state, env.get_actions());
+                action_value
+                    .get_features()
+                    .get_features(&next_state, greedy_action)
+            };
+            let weights = action_value.get_weights_mut();
+            let td_error = reward + config.discount_factor * next_features.dot(weights)
+                - features.dot(weights);
+            let prediction =
+                update_secondary_weights(&mut secondary_weights, config, &features, td_error, 1.0);
+            // update primary weights
+            features.add_scaled_to(weights, config.learning_rate * td_error);
+            next_features.add_scaled_to(
+                weights,
+                -config.learning_rate * config.discount_factor * prediction,
+            );
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    action_value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::baird::{BairdAction, BairdCounterexample, BairdFeatures, BairdState};
+    use crate::environments::gridworld::{assert_policy_optimal, get_gridworld, get_test_config};
+    use crate::models::{EpsilonGreedy, MDPEnvironment, OneHot, PerActionFeatures, MDP};
+    use crate::solvers::approximation::off_policy_semi_gradient_td;
+
+    fn get_baird_config() -> Config {
+        Config::new()
+            .discount_factor(0.99)
+            .learning_rate(0.005)
+            .secondary_learning_rate(0.05)
+            .num_episodes(1)
+            .max_num_steps(1_000)
+    }
+
+    /// Learns the value of the target policy of Baird's counterexample
+    /// with the given solver and returns the highest absolute value of a state,
+    /// knowing that the true value of every state is zero
+    /// and that the highest absolute initial value is twelve
+    fn learn_baird<G>(solver: G) -> f64
+    where
+        G: Fn(
+            &mut BairdCounterexample,
+            &Config,
+            LinearStateValue<BairdState, BairdFeatures>,
+            &StochasticPolicy<BairdState, BairdAction>,
+            &StochasticPolicy<BairdState, BairdAction>,
+        ) -> LinearStateValue<BairdState, BairdFeatures>,
+    {
+        let mut env = BairdCounterexample::new();
+        let state_value = LinearStateValue::with_weights(BairdFeatures, env.get_initial_weights());
+        let target = env.get_target_policy();
+        let behaviour = env.get_behaviour_policy();
+        let state_value = solver(
+            &mut env,
+            &get_baird_config(),
+            state_value,
+            &target,
+            &behaviour,
+        );
+        env.get_states()
+            .iter()
+            .map(|state| state_value.get(state).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn semi_gradient_td_diverges_on_baird() {
+        assert!(learn_baird(off_policy_semi_gradient_td) > 50.0);
+    }
+
+    #[test]
+    fn test_tdc_on_baird() {
+        assert!(learn_baird(tdc) < 5.0);
+    }
+
+    #[test]
+    fn test_gtd2_on_baird() {
+        assert!(learn_baird(gtd2) < 5.0);
+    }
+
+    #[test]
+    fn test_greedy_gq() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let features = PerActionFeatures::new(OneHot::new(mdp.get_states()), mdp.get_actions());
+        let action_value = greedy_gq(
+            &mut env,
+            &config,
+            &mut exploration,
+            LinearActionValue::new(features),
+        );
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+}
@@ -0,0 +1,698 @@
+//! # heuristic_search
+//!
+//! The `heuristic_search` module contains the implementations of the heuristic search algorithms.
+//! Instead of sweeping over every state, they only update the states relevant to an initial state,
+//! starting from the values given by a heuristic.
+//! The heuristic should be admissible, that is never lower than the optimal value of a state,
+//! for the solvers to find the optimal values of the relevant states.
+//! The solvers never enumerate the states with [`MDP::get_states`]
+//! and return a [`PartialSolution`] defined on the states they visited.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::models::{Config, PartialSolution, Policy, Sampler, StateValue, MDP};
+
+/// The values of the states visited by a heuristic search solver,
+/// given by the heuristic until they are updated.
+/// Terminal states have a value of zero.
+struct HeuristicValue<'a, M, H>
+where
+    M: MDP,
+{
+    mdp: &'a M,
+    heuristic: H,
+    values: HashMap<M::State, f64>,
+}
+
+impl<'a, M, H> HeuristicValue<'a, M, H>
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    fn new(mdp: &'a M, heuristic: H) -> Self {
+        Self {
+            mdp,
+            heuristic,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Returns the value of the given state.
+    fn get(&self, state: &M::State) -> f64 {
+        if self.mdp.is_state_terminal(state) {
+            return 0.0;
+        }
+        match self.values.get(state) {
+            Some(value) => *value,
+            None => (self.heuristic)(state),
+        }
+    }
+
+    /// Returns the greedy action of the given state along with its value and next state.
+    fn greedy(&self, config: &Config, state: &M::State) -> (M::Action, f64, M::State) {
+        let mut best = None;
+        for action in self.mdp.get_actions() {
+            let (next_state, reward) = self.mdp.transition(state, action);
+            let value = reward + config.discount_factor * self.get(&next_state);
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_value, _)| value > *best_value)
+            {
+                best = Some((action.clone(), value, next_state));
+            }
+        }
+        // unwrap is safe because actions is not empty
+        best.unwrap()
+    }
+
+    /// Returns the difference between the value of the given state and its Bellman backup.
+    fn get_residual(&self, config: &Config, state: &M::State) -> f64 {
+        let (_, value, _) = self.greedy(config, state);
+        (value - self.get(state)).abs()
+    }
+
+    /// Performs a Bellman backup of the given state
+    /// and returns the next state of its greedy action.
+    fn update(&mut self, config: &Config, state: &M::State) -> M::State {
+        let (_, value, next_state) = self.greedy(config, state);
+        self.values.insert(state.clone(), value);
+        next_state
+    }
+
+    /// Returns whether the residual of every state reached
+    /// by following the greedy policy from the given state is within the tolerance.
+    fn is_converged(&self, config: &Config, state: &M::State) -> bool {
+        let mut seen = HashSet::new();
+        let mut current = state.clone();
+        while !self.mdp.is_state_terminal(&current) && seen.insert(current.clone()) {
+            if self.get_residual(config, &current) > config.tolerance {
+                return false;
+            }
+            let (_, _, next_state) = self.greedy(config, &current);
+            current = next_state;
+        }
+        true
+    }
+
+    /// Returns the greedy policy and the values of the visited states.
+    fn into_solution(
+        self,
+        config: &Config,
+        state: &M::State,
+        solved: bool,
+    ) -> PartialSolution<M::State, M::Action> {
+        let mut visited: Vec<M::State> = self.values.keys().cloned().collect();
+        if !self.values.contains_key(state) {
+            visited.push(state.clone());
+        }
+        let visited = Sampler::new(visited);
+        let mut policy = Policy::new(&visited, self.mdp.get_actions());
+        let mut state_value = StateValue::new(&visited);
+        for state in &visited {
+            let (action, _, _) = self.greedy(config, state);
+            policy.insert(state, &action);
+            state_value.insert(state, self.get(state));
+        }
+        PartialSolution {
+            policy,
+            state_value,
+            solved,
+        }
+    }
+}
+
+/// # Real-Time Dynamic Programming
+///
+/// This function implements the RTDP algorithm.
+/// Each trial starts from the given state and follows the greedy policy
+/// with respect to the current values,
+/// performing a Bellman backup of each state it visits.
+/// The values of the states are initialised with the given heuristic when first needed.
+/// A trial ends in a terminal state
+/// or after the `max_num_steps` parameter in the config.
+/// The algorithm stops after the `num_episodes` parameter in the config,
+/// or as soon as the residual of every state reached by the greedy policy from the given state
+/// is within the `tolerance` parameter, in which case the solution is marked as solved.
+pub fn rtdp<M, H>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+    heuristic: H,
+) -> PartialSolution<M::State, M::Action>
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let mut value = HeuristicValue::new(mdp, heuristic);
+    let mut solved = false;
+    for _ in 0..config.num_episodes {
+        let mut current = state.clone();
+        for _ in 0..config.max_num_steps {
+            if mdp.is_state_terminal(&current) {
+                break;
+            }
+            current = value.update(config, &current);
+        }
+        if value.is_converged(config, state) {
+            solved = true;
+            break;
+        }
+    }
+    value.into_solution(config, state, solved)
+}
+
+/// Labels as solved the states reached by following the greedy policy from the given state
+/// if all their residuals are within the tolerance,
+/// or updates them otherwise.
+/// Returns whether the states were labelled.
+fn check_solved<M, H>(
+    value: &mut HeuristicValue<M, H>,
+    solved_states: &mut HashSet<M::State>,
+    config: &Config,
+    state: &M::State,
+) -> bool
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let mut converged = true;
+    let mut open = vec![];
+    let mut closed = vec![];
+    let mut seen = HashSet::new();
+    if !solved_states.contains(state) {
+        open.push(state.clone());
+        seen.insert(state.clone());
+    }
+    while let Some(current) = open.pop() {
+        if !value.mdp.is_state_terminal(&current) {
+            if value.get_residual(config, &current) > config.tolerance {
+                converged = false;
+            } else {
+                let (_, _, next_state) = value.greedy(config, &current);
+                if !solved_states.contains(&next_state) && seen.insert(next_state.clone()) {
+                    open.push(next_state);
+                }
+            }
+        }
+        closed.push(current);
+    }
+    if converged {
+        solved_states.extend(closed);
+    } else {
+        for current in closed.iter().rev() {
+            if !value.mdp.is_state_terminal(current) {
+                value.update(config, current);
+            }
+        }
+    }
+    converged
+}
+
+/// # Labeled Real-Time Dynamic Programming
+///
+/// This function implements the LRTDP algorithm.
+/// It works like [`rtdp`], except that it labels as solved the states
+/// whose value and the values of the states reached from them by the greedy policy
+/// are within the `tolerance` parameter in the config.
+/// Trials end as soon as they reach a solved state,
+/// and the states of each trial are checked in reverse order at its end.
+/// The algorithm stops when the given state is labelled as solved,
+/// in which case the solution is marked as solved,
+/// or after the `num_episodes` parameter in the config.
+pub fn lrtdp<M, H>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+    heuristic: H,
+) -> PartialSolution<M::State, M::Action>
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let mut value = HeuristicValue::new(mdp, heuristic);
+    let mut solved_states = HashSet::new();
+    for _ in 0..config.num_episodes {
+        if solved_states.contains(state) {
+            break;
+        }
+        let mut visited = vec![];
+        let mut current = state.clone();
+        for _ in 0..config.max_num_steps {
+            if solved_states.contains(&current) {
+                break;
+            }
+            visited.push(current.clone());
+            if mdp.is_state_terminal(&current) {
+                break;
+            }
+            current = value.update(config, &current);
+        }
+        while let Some(current) = visited.pop() {
+            if !check_solved(&mut value, &mut solved_states, config, &current) {
+                break;
+            }
+        }
+    }
+    let solved = solved_states.contains(state);
+    value.into_solution(config, state, solved)
+}
+
+/// Returns the expanded states of the best partial solution graph from the given state,
+/// along with its first tip, a non-terminal state that has not been expanded, if any.
+/// Since transitions are deterministic, the graph is the path of the greedy policy,
+/// which ends in a terminal state, a tip or a cycle.
+fn get_solution_graph<M, H>(
+    value: &HeuristicValue<M, H>,
+    expanded: &HashSet<M::State>,
+    config: &Config,
+    state: &M::State,
+) -> (Vec<M::State>, Option<M::State>)
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let mut graph = vec![];
+    let mut seen = HashSet::new();
+    let mut current = state.clone();
+    while !value.mdp.is_state_terminal(&current) && seen.insert(current.clone()) {
+        if !expanded.contains(&current) {
+            return (graph, Some(current));
+        }
+        let (_, _, next_state) = value.greedy(config, &current);
+        graph.push(current);
+        current = next_state;
+    }
+    (graph, None)
+}
+
+/// Performs Bellman backups of the given states until their residuals are within the tolerance
+/// or after the `max_num_iterations` parameter in the config.
+/// Returns whether the values converged.
+fn update_until_converged<M, H>(
+    value: &mut HeuristicValue<M, H>,
+    config: &Config,
+    states: &[M::State],
+) -> bool
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    for _ in 0..config.max_num_iterations {
+        let mut max_residual: f64 = 0.0;
+        for state in states {
+            max_residual = max_residual.max(value.get_residual(config, state));
+            value.update(config, state);
+        }
+        if max_residual <= config.tolerance {
+            return true;
+        }
+    }
+    false
+}
+
+/// # LAO*
+///
+/// This function implements the LAO* algorithm,
+/// a heuristic search algorithm for goal-directed MDPs,
+/// such as stochastic shortest path problems
+/// whose rewards are negative costs and whose discount factor can be one.
+/// It grows an explicit graph of expanded states from the given state,
+/// whose values are initialised with the given heuristic.
+/// At each iteration, a tip of the best partial solution graph,
+/// the states reached by following the greedy policy from the given state, is expanded
+/// and the values of the solution graph are updated with value iteration.
+/// When the solution graph has no tip left, its values are updated until convergence,
+/// and the algorithm stops if it still has no tip, in which case the solution is marked as solved.
+/// Value iteration stops when the residuals are within the `tolerance` parameter in the config.
+/// The number of iterations of the algorithm and of each value iteration
+/// is limited by the `max_num_iterations` parameter in the config.
+pub fn lao_star<M, H>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+    heuristic: H,
+) -> PartialSolution<M::State, M::Action>
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let mut value = HeuristicValue::new(mdp, heuristic);
+    let mut expanded = HashSet::new();
+    let mut solved = false;
+    for _ in 0..config.max_num_iterations {
+        let (mut graph, tip) = get_solution_graph(&value, &expanded, config, state);
+        match tip {
+            Some(tip) => {
+                expanded.insert(tip.clone());
+                graph.push(tip);
+                update_until_converged(&mut value, config, &graph);
+            }
+            None => {
+                let converged = update_until_converged(&mut value, config, &graph);
+                let (_, tip) = get_solution_graph(&value, &expanded, config, state);
+                if converged && tip.is_none() {
+                    solved = true;
+                    break;
+                }
+            }
+        }
+    }
+    value.into_solution(config, state, solved)
+}
+
+/// # ILAO*
+///
+/// This function implements the improved LAO* algorithm.
+/// It works like [`lao_star`], except that each iteration traverses
+/// the best partial solution graph depth-first, expanding every tip it reaches,
+/// and performs a single Bellman backup of each of its states in postorder
+/// instead of running value iteration.
+/// The algorithm stops when an iteration expands no state
+/// and the residuals of the solution graph are within the `tolerance` parameter in the config,
+/// in which case the solution is marked as solved,
+/// or after the `max_num_iterations` parameter in the config.
+pub fn ilao_star<M, H>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+    heuristic: H,
+) -> PartialSolution<M::State, M::Action>
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let mut value = HeuristicValue::new(mdp, heuristic);
+    let mut expanded = HashSet::new();
+    let mut solved = false;
+    for _ in 0..config.max_num_iterations {
+        // since transitions are deterministic, the depth-first traversal is a path
+        let mut traversal = vec![];
+        let mut num_expanded = 0;
+        let mut current = state.clone();
+        while !mdp.is_state_terminal(&current) && !traversal.contains(&current) {
+            traversal.push(current.clone());
+            if expanded.insert(current.clone()) {
+                num_expanded += 1;
+                break;
+            }
+            let (_, _, next_state) = value.greedy(config, &current);
+            current = next_state;
+        }
+        let mut max_residual: f64 = 0.0;
+        for current in traversal.iter().rev() {
+            max_residual = max_residual.max(value.get_residual(config, current));
+            value.update(config, current);
+        }
+        if num_expanded == 0 && max_residual <= config.tolerance {
+            solved = true;
+            break;
+        }
+    }
+    value.into_solution(config, state, solved)
+}
+
+/// A state in the priority queue of A*,
+/// ordered by the sum of its return and its heuristic value
+struct QueueEntry<S> {
+    priority: f64,
+    state: S,
+}
+
+impl<S> PartialEq for QueueEntry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority.total_cmp(&other.priority).is_eq()
+    }
+}
+
+impl<S> Eq for QueueEntry<S> {}
+
+impl<S> PartialOrd for QueueEntry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> Ord for QueueEntry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.total_cmp(&other.priority)
+    }
+}
+
+/// # A*
+///
+/// This function implements the A* algorithm
+/// to find the path from the given state to a terminal state with the highest return,
+/// which is the shortest path when the rewards are negative costs.
+/// The states are expanded in decreasing order of the sum of the return from the given state
+/// and of their heuristic value, the estimated return from them to a terminal state.
+/// The heuristic must be consistent, that is never lower than the reward of a transition
+/// plus the heuristic value of the next state,
+/// and terminal states have a heuristic value of zero.
+/// Unlike the other solvers, the return is the undiscounted sum of the rewards,
+/// so the discount factor is ignored.
+/// The number of expanded states is limited by the `max_num_iterations` parameter in the config.
+/// The solution is defined on the non-terminal states of the path and is marked as solved.
+/// If no terminal state is found, the solution is not solved
+/// and only contains the given state, with its heuristic value.
+pub fn a_star<M, H>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+    heuristic: H,
+) -> PartialSolution<M::State, M::Action>
+where
+    M: MDP,
+    H: Fn(&M::State) -> f64,
+{
+    let get_heuristic = |state: &M::State| {
+        if mdp.is_state_terminal(state) {
+            0.0
+        } else {
+            heuristic(state)
+        }
+    };
+    let mut returns = HashMap::from([(state.clone(), 0.0)]);
+    let mut parents: HashMap<M::State, (M::State, M::Action)> = HashMap::new();
+    let mut closed = HashSet::new();
+    let mut queue = BinaryHeap::from([QueueEntry {
+        priority: get_heuristic(state),
+        state: state.clone(),
+    }]);
+    let mut goal = None;
+    for _ in 0..config.max_num_iterations {
+        let Some(QueueEntry { state: current, .. }) = queue.pop() else {
+            break;
+        };
+        if !closed.insert(current.clone()) {
+            continue;
+        }
+        if mdp.is_state_terminal(&current) {
+            goal = Some(current);
+            break;
+        }
+        let return_ = returns[&current];
+        for action in mdp.get_actions() {
+            let (next_state, reward) = mdp.transition(&current, action);
+            if closed.contains(&next_state) {
+                continue;
+            }
+            let next_return = return_ + reward;
+            if returns
+                .get(&next_state)
+                .is_none_or(|known_return| next_return > *known_return)
+            {
+                returns.insert(next_state.clone(), next_return);
+                parents.insert(next_state.clone(), (current.clone(), action.clone()));
+                queue.push(QueueEntry {
+                    priority: next_return + get_heuristic(&next_state),
+                    state: next_state,
+                });
+            }
+        }
+    }
+    // reconstruct the path from the goal
+    let mut path = vec![];
+    if let Some(goal) = &goal {
+        let mut current = goal.clone();
+        while let Some((parent, action)) = parents.remove(&current) {
+            path.push((parent.clone(), action));
+            current = parent;
+        }
+    }
+    if path.is_empty() {
+        let states = Sampler::new(vec![state.clone()]);
+        let mut state_value = StateValue::new(&states);
+        state_value.insert(state, get_heuristic(state));
+        return PartialSolution {
+            policy: Policy::new(&states, mdp.get_actions()),
+            state_value,
+            solved: goal.is_some(),
+        };
+    }
+    // unwrap is safe because the path is not empty so a goal was found
+    let goal_return = returns[goal.as_ref().unwrap()];
+    let states = Sampler::new(path.iter().map(|(state, _)| state.clone()).collect());
+    let mut policy = Policy::new(&states, mdp.get_actions());
+    let mut state_value = StateValue::new(&states);
+    for (state, action) in &path {
+        policy.insert(state, action);
+        state_value.insert(state, goal_return - returns[state]);
+    }
+    PartialSolution {
+        policy,
+        state_value,
+        solved: true,
+    }
+}
+
+/// # Dijkstra
+///
+/// This function implements Dijkstra's algorithm,
+/// which is [`a_star`] with a heuristic of zero.
+/// The rewards must not be positive, that is the costs must not be negative,
+/// for the path found to have the highest return.
+pub fn dijkstra<M>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+) -> PartialSolution<M::State, M::Action>
+where
+    M: MDP,
+{
+    a_star(mdp, config, state, |_| 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        get_maze, get_test_config, Cell, Gridworld, GridworldAction, GridworldState,
+    };
+    use crate::models::{LinearRewardMDP, OneHot, StateFeatures};
+    use crate::solvers::dynamic_programming::policy_iteration;
+
+    /// The highest reward of the maze, which is an upper bound of the value of any state
+    fn heuristic(_: &GridworldState) -> f64 {
+        100.0
+    }
+
+    /// Checks that the values of the states on the path of the policy
+    /// from the top left corner are optimal
+    fn assert_solution_optimal(
+        mdp: &Gridworld,
+        solution: &PartialSolution<GridworldState, GridworldAction>,
+    ) {
+        let config = get_test_config();
+        let optimal_value = policy_iteration(mdp, &config);
+        let mut state = GridworldState::new(0, 0);
+        while !mdp.is_state_terminal(&state) {
+            let value = solution.state_value.get(&state);
+            assert!((value - optimal_value.get(&state)).abs() < 1e-3);
+            state = mdp.transition(&state, solution.policy.get(&state)).0;
+        }
+    }
+
+    #[test]
+    fn test_rtdp() {
+        let mdp = get_maze();
+        let config = get_test_config().tolerance(1e-6);
+        let solution = rtdp(&mdp, &config, &GridworldState::new(0, 0), heuristic);
+        assert!(solution.solved);
+        assert_solution_optimal(&mdp, &solution);
+    }
+
+    #[test]
+    fn test_lrtdp() {
+        let mdp = get_maze();
+        let config = get_test_config().tolerance(1e-6);
+        let solution = lrtdp(&mdp, &config, &GridworldState::new(0, 0), heuristic);
+        assert!(solution.solved);
+        assert_solution_optimal(&mdp, &solution);
+    }
+
+    #[test]
+    fn test_lrtdp_budget_exhausted() {
+        let mdp = get_maze();
+        let config = get_test_config().num_episodes(1).max_num_steps(1);
+        let solution = lrtdp(&mdp, &config, &GridworldState::new(0, 0), heuristic);
+        assert!(!solution.solved);
+    }
+
+    #[test]
+    fn test_lao_star() {
+        let mdp = get_maze();
+        let config = get_test_config().tolerance(1e-6);
+        let solution = lao_star(&mdp, &config, &GridworldState::new(0, 0), heuristic);
+        assert!(solution.solved);
+        assert_solution_optimal(&mdp, &solution);
+    }
+
+    #[test]
+    fn test_ilao_star() {
+        let mdp = get_maze();
+        let config = get_test_config().tolerance(1e-6);
+        let solution = ilao_star(&mdp, &config, &GridworldState::new(0, 0), heuristic);
+        assert!(solution.solved);
+        assert_solution_optimal(&mdp, &solution);
+    }
+
+    /// Follows the policy of the solution from the top left corner
+    /// and returns the number of steps to the end of the maze
+    fn get_path_length<M>(
+        mdp: &M,
+        solution: &PartialSolution<GridworldState, GridworldAction>,
+    ) -> u32
+    where
+        M: MDP<State = GridworldState, Action = GridworldAction>,
+    {
+        let mut state = GridworldState::new(0, 0);
+        let mut length = 0;
+        while !mdp.is_state_terminal(&state) {
+            state = mdp.transition(&state, solution.policy.get(&state)).0;
+            length += 1;
+        }
+        length
+    }
+
+    #[test]
+    fn test_a_star() {
+        let mdp = get_maze();
+        let config = get_test_config();
+        // consistent since every move costs at least one before the final reward
+        let solution = a_star(&mdp, &config, &GridworldState::new(0, 0), heuristic);
+        assert!(solution.solved);
+        assert_eq!(get_path_length(&mdp, &solution), 6);
+        assert_eq!(solution.state_value.get(&GridworldState::new(0, 0)), 95.0);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let maze = get_maze();
+        let features = OneHot::new(maze.get_states());
+        let costs = vec![-1.0; features.get_num_features()];
+        // every move costs one, including the last one
+        let mdp = LinearRewardMDP::new(&maze, &features, &costs);
+        let solution = dijkstra(&mdp, &get_test_config(), &GridworldState::new(0, 0));
+        assert!(solution.solved);
+        assert_eq!(get_path_length(&mdp, &solution), 6);
+        assert_eq!(solution.state_value.get(&GridworldState::new(0, 0)), -6.0);
+    }
+
+    #[test]
+    fn test_a_star_without_goal() {
+        let mdp = Gridworld::new(
+            vec![vec![Cell::Air, Cell::Wall, Cell::End]],
+            vec![GridworldState::new(0, 0), GridworldState::new(0, 2)],
+            vec![GridworldAction::Left, GridworldAction::Right],
+        );
+        let solution = a_star(
+            &mdp,
+            &get_test_config(),
+            &GridworldState::new(0, 0),
+            heuristic,
+        );
+        assert!(!solution.solved);
+        assert_eq!(solution.state_value.get(&GridworldState::new(0, 0)), 100.0);
+    }
+}
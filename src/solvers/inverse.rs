@@ -0,0 +1,350 @@
+//! # inverse
+//!
+//! The `inverse` module contains the implementations of the inverse reinforcement learning algorithms.
+//! Instead of learning a policy from rewards, they recover a reward from demonstrations of an expert.
+//! The reward is linear in the given state features:
+//! the reward of a transition is the dot product of the weights
+//! with the features of the state it leads to, as in a [`LinearRewardMDP`].
+//! The solvers ignore the rewards of the given [`MDP`]
+//! and only use its states, actions and transitions.
+
+use std::collections::HashMap;
+
+use crate::models::{
+    dot, Action, Config, Episode, LinearRewardMDP, Policy, PolicyMixture, State, StateFeatures,
+    StochasticPolicy, WeightsAndMixture, WeightsAndPolicy, MDP,
+};
+use crate::solvers::dynamic_programming::soft_value_iteration;
+
+/// Returns the average discounted sum of the features
+/// of the states reached during the given demonstrations.
+fn get_expert_feature_expectations<S, A, F>(
+    config: &Config,
+    features: &F,
+    demonstrations: &[Episode<S, A>],
+) -> Vec<f64>
+where
+    S: State,
+    A: Action,
+    F: StateFeatures<S>,
+{
+    let mut feature_expectations = vec![0.0; features.get_num_features()];
+    for episode in demonstrations {
+        let mut discount = 1.0;
+        for transition in episode {
+            features
+                .get_features(&transition.next_state)
+                .add_scaled_to(&mut feature_expectations, discount);
+            discount *= config.discount_factor;
+        }
+    }
+    let num_demonstrations = demonstrations.len() as f64;
+    for feature_expectation in &mut feature_expectations {
+        *feature_expectation /= num_demonstrations;
+    }
+    feature_expectations
+}
+
+/// Returns the expected discounted sum of the features
+/// of the states reached by following the given policy
+/// from the first states of the given demonstrations.
+/// The state visitation frequencies are propagated through the transitions
+/// for at most `max_num_steps` steps,
+/// or until the probability of still being in a non-terminal state
+/// is lower than the `tolerance` parameter in the config.
+fn get_policy_feature_expectations<M, F>(
+    mdp: &M,
+    config: &Config,
+    features: &F,
+    policy: &StochasticPolicy<M::State, M::Action>,
+    demonstrations: &[Episode<M::State, M::Action>],
+) -> Vec<f64>
+where
+    M: MDP,
+    F: StateFeatures<M::State>,
+{
+    let mut feature_expectations = vec![0.0; features.get_num_features()];
+    let mut visitation: HashMap<M::State, f64> = HashMap::new();
+    let probability = 1.0 / demonstrations.len() as f64;
+    for episode in demonstrations {
+        if let Some(transition) = episode.iter().next() {
+            *visitation.entry(transition.state.clone()).or_insert(0.0) += probability;
+        }
+    }
+    let mut discount = 1.0;
+    for _ in 0..config.max_num_steps {
+        let mut next_visitation = HashMap::new();
+        for (state, state_probability) in &visitation {
+            for action in mdp.get_actions() {
+                let probability = state_probability * policy.get(state, action);
+                let (next_state, _) = mdp.transition(state, action);
+                features
+                    .get_features(&next_state)
+                    .add_scaled_to(&mut feature_expectations, discount * probability);
+                if !mdp.is_state_terminal(&next_state) {
+                    *next_visitation.entry(next_state).or_insert(0.0) += probability;
+                }
+            }
+        }
+        visitation = next_visitation;
+        discount *= config.discount_factor;
+        if visitation.values().sum::<f64>() < config.tolerance {
+            break;
+        }
+    }
+    feature_expectations
+}
+
+/// # Maximum Entropy Inverse Reinforcement Learning
+///
+/// This function implements the maximum entropy inverse reinforcement learning algorithm.
+/// It looks for the reward weights under which the soft-optimal policy,
+/// computed with [`soft_value_iteration`],
+/// visits the same features as the demonstrations on average.
+/// At each iteration, the weights are moved with the `learning_rate` parameter in the config
+/// along the gradient of the log-likelihood of the demonstrations,
+/// which is the difference between the discounted feature expectations
+/// of the demonstrations and of the soft-optimal policy
+/// started from the first states of the demonstrations.
+/// The algorithm stops when no component of the gradient is larger than the `tolerance` parameter
+/// or after the `max_num_iterations` parameter in the config.
+/// Returns the learned reward weights along with the soft-optimal policy for them.
+pub fn max_ent_irl<M, F>(
+    mdp: &M,
+    config: &Config,
+    features: &F,
+    demonstrations: &[Episode<M::State, M::Action>],
+) -> WeightsAndPolicy<M::State, M::Action>
+where
+    M: MDP,
+    F: StateFeatures<M::State>,
+{
+    assert!(
+        !demonstrations.is_empty(),
+        "there must be at least one demonstration."
+    );
+    let expert_feature_expectations =
+        get_expert_feature_expectations(config, features, demonstrations);
+    let mut weights = vec![0.0; features.get_num_features()];
+    for _ in 0..config.max_num_iterations {
+        let reward_mdp = LinearRewardMDP::new(mdp, features, &weights);
+        let (policy, _) = soft_value_iteration(&reward_mdp, config);
+        let feature_expectations =
+            get_policy_feature_expectations(mdp, config, features, &policy, demonstrations);
+        let mut max_gradient: f64 = 0.0;
+        for ((weight, expert), learner) in weights
+            .iter_mut()
+            .zip(&expert_feature_expectations)
+            .zip(&feature_expectations)
+        {
+            let gradient = expert - learner;
+            *weight += config.learning_rate * gradient;
+            max_gradient = max_gradient.max(gradient.abs());
+        }
+        if max_gradient < config.tolerance {
+            break;
+        }
+    }
+    let reward_mdp = LinearRewardMDP::new(mdp, features, &weights);
+    let (policy, _) = soft_value_iteration(&reward_mdp, config);
+    (weights, policy)
+}
+
+/// # Apprenticeship Learning
+///
+/// This function implements the projection algorithm of apprenticeship learning
+/// by Abbeel and Ng.
+/// It looks for policies whose discounted feature expectations,
+/// started from the first states of the demonstrations,
+/// match the ones of the demonstrations.
+/// At each iteration, the reward weights are the difference between the feature expectations
+/// of the demonstrations and their projection on the policies found so far,
+/// and the given planner computes a new policy optimal for these weights,
+/// for instance with
+/// [`policy_iteration`](crate::solvers::dynamic_programming::policy_iteration)
+/// and [`policy_improvement`](crate::solvers::dynamic_programming::policy_improvement).
+/// The first policy is planned with zero weights.
+/// The algorithm stops when the margin, the norm of the weights,
+/// is smaller than the `tolerance` parameter
+/// or after the `max_num_iterations` parameter in the config.
+/// Returns the last reward weights along with the mixture of the planned policies
+/// whose feature expectations are the projection.
+pub fn apprenticeship_learning<M, F, P>(
+    mdp: &M,
+    config: &Config,
+    features: &F,
+    demonstrations: &[Episode<M::State, M::Action>],
+    mut planner: P,
+) -> WeightsAndMixture<M::State, M::Action>
+where
+    M: MDP,
+    F: StateFeatures<M::State>,
+    P: FnMut(&LinearRewardMDP<'_, M, F>) -> Policy<M::State, M::Action>,
+{
+    assert!(
+        !demonstrations.is_empty(),
+        "there must be at least one demonstration."
+    );
+    let expert_feature_expectations =
+        get_expert_feature_expectations(config, features, demonstrations);
+    let mut weights = vec![0.0; features.get_num_features()];
+    let mut policies = vec![];
+    let mut mixture_weights = vec![];
+    let mut projection = vec![];
+    for _ in 0..config.max_num_iterations {
+        let policy = planner(&LinearRewardMDP::new(mdp, features, &weights));
+        let feature_expectations = get_policy_feature_expectations(
+            mdp,
+            config,
+            features,
+            &StochasticPolicy::from(&policy),
+            demonstrations,
+        );
+        policies.push(policy);
+        if projection.is_empty() {
+            projection = feature_expectations;
+            mixture_weights.push(1.0);
+        } else {
+            // project the expert feature expectations
+            // on the line between the previous projection and the new feature expectations
+            let direction: Vec<f64> = feature_expectations
+                .iter()
+                .zip(&projection)
+                .map(|(new, old)| new - old)
+                .collect();
+            let squared_norm = dot(&direction, &direction);
+            let ratio = if squared_norm > 0.0 {
+                (dot(&direction, &weights) / squared_norm).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            for (old, step) in projection.iter_mut().zip(&direction) {
+                *old += ratio * step;
+            }
+            for mixture_weight in &mut mixture_weights {
+                *mixture_weight *= 1.0 - ratio;
+            }
+            mixture_weights.push(ratio);
+        }
+        weights = expert_feature_expectations
+            .iter()
+            .zip(&projection)
+            .map(|(expert, projected)| expert - projected)
+            .collect();
+        if dot(&weights, &weights).sqrt() < config.tolerance {
+            break;
+        }
+    }
+    (weights, PolicyMixture::new(policies, mixture_weights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{Cell, Gridworld, GridworldAction, GridworldState};
+    use crate::models::{MDPEnvironment, OneHot};
+    use crate::solvers::dynamic_programming::{
+        policy_evaluation, policy_improvement, policy_iteration,
+    };
+
+    fn get_grid() -> Gridworld {
+        let mut states = vec![];
+        for i in 0..3 {
+            for j in 0..3 {
+                states.push(GridworldState::new(i, j));
+            }
+        }
+        Gridworld::new(
+            vec![
+                vec![Cell::Air, Cell::Air, Cell::Air],
+                vec![Cell::Air, Cell::Wall, Cell::Air],
+                vec![Cell::Air, Cell::Air, Cell::End],
+            ],
+            states
+                .into_iter()
+                .filter(|s| s != &GridworldState::new(1, 1))
+                .collect(),
+            vec![
+                GridworldAction::Down,
+                GridworldAction::Left,
+                GridworldAction::Right,
+                GridworldAction::Up,
+            ],
+        )
+    }
+
+    fn get_config() -> Config {
+        Config::new()
+            .discount_factor(0.9)
+            .learning_rate(0.5)
+            .max_num_iterations(200)
+            .max_num_steps(50)
+            .tolerance(1e-4)
+    }
+
+    fn get_demonstrations(
+        mdp: &Gridworld,
+        config: &Config,
+        expert: &Policy<GridworldState, GridworldAction>,
+    ) -> Vec<Episode<GridworldState, GridworldAction>> {
+        let mut env = MDPEnvironment::new(mdp);
+        (0..50)
+            .map(|_| Episode::rollout(&mut env, expert, config.max_num_steps))
+            .collect()
+    }
+
+    #[test]
+    fn test_max_ent_irl() {
+        let mdp = get_grid();
+        let config = get_config();
+        let state_value = policy_iteration(&mdp, &config);
+        let expert = policy_improvement(&mdp, &config, &state_value);
+        let demonstrations = get_demonstrations(&mdp, &config, &expert);
+        let features = OneHot::new(mdp.get_states());
+        let (weights, policy) = max_ent_irl(&mdp, &config, &features, &demonstrations);
+        // the goal has the highest learned reward
+        let goal = features
+            .get_features(&GridworldState::new(2, 2))
+            .dot(&weights);
+        for state in mdp.get_states() {
+            assert!(features.get_features(state).dot(&weights) <= goal);
+        }
+        // the most likely policy is optimal for the true reward
+        let policy = policy.most_likely_policy(mdp.get_states(), mdp.get_actions());
+        let learned_value = policy_evaluation(&mdp, &config, &policy, None);
+        for state in mdp.get_states() {
+            assert!((learned_value.get(state) - state_value.get(state)).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_apprenticeship_learning() {
+        let mdp = get_grid();
+        let config = get_config();
+        let state_value = policy_iteration(&mdp, &config);
+        let expert = policy_improvement(&mdp, &config, &state_value);
+        let demonstrations = get_demonstrations(&mdp, &config, &expert);
+        let features = OneHot::new(mdp.get_states());
+        let (_, mixture) =
+            apprenticeship_learning(&mdp, &config, &features, &demonstrations, |reward_mdp| {
+                let state_value = policy_iteration(reward_mdp, &config);
+                policy_improvement(reward_mdp, &config, &state_value)
+            });
+        // the mixture is as good as the expert for the true reward
+        let get_average_value = |policy| {
+            let state_value = policy_evaluation(&mdp, &config, policy, None);
+            let total: f64 = demonstrations
+                .iter()
+                .map(|episode| state_value.get(&episode.iter().next().unwrap().state))
+                .sum();
+            total / demonstrations.len() as f64
+        };
+        let mixture_value: f64 = mixture
+            .get_policies()
+            .iter()
+            .zip(mixture.get_weights())
+            .map(|(policy, weight)| weight * get_average_value(policy))
+            .sum();
+        assert!((mixture_value - get_average_value(&expert)).abs() < 1e-1);
+    }
+}
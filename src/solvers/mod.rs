@@ -2,5 +2,15 @@
 //!
 //! The `solvers` module contains the implementations of the different algorithms.
 
+pub mod approximation;
+pub mod bandit;
+pub mod batch;
 pub mod dynamic_programming;
+pub mod gradient_temporal_difference;
+pub mod heuristic_search;
+pub mod inverse;
+pub mod model_based;
+pub mod planning;
+pub mod policy_gradient;
+pub mod stochastic_shortest_path;
 pub mod temporal_difference;
@@ -0,0 +1,630 @@
+//! # model_based
+//!
+//! The `model_based` module contains the implementations of the model-based exploration algorithms.
+//! The agents only interact with the problem through the [`Environment`] trait.
+//! They record the transitions they observe in an empirical model
+//! and act greedily with respect to an optimistic version of it,
+//! so that they are drawn to the state-action pairs they know little about.
+//! Each agent returns the learned policy along with the number of samples used,
+//! that is the number of steps taken in the environment.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{Action, Config, Environment, Policy, Sampler, State, StateValue, MDP};
+use crate::solvers::dynamic_programming::{policy_improvement, policy_value_iteration};
+
+/// The transitions observed from a state-action pair.
+#[derive(Debug, Clone)]
+struct PairStatistics<S>
+where
+    S: State,
+{
+    num_visits: u32,
+    total_reward: f64,
+    next_states: HashMap<S, u32>,
+}
+
+impl<S> PairStatistics<S>
+where
+    S: State,
+{
+    /// Returns the average observed reward.
+    fn get_mean_reward(&self) -> f64 {
+        self.total_reward / f64::from(self.num_visits)
+    }
+
+    /// Returns the most frequently observed next state.
+    fn get_most_frequent_next_state(&self) -> &S {
+        let (next_state, _) = self
+            .next_states
+            .iter()
+            .reduce(|best, other| if other.1 > best.1 { other } else { best })
+            .expect("a visited pair must have at least one next state.");
+        next_state
+    }
+
+    /// Returns the observed frequency of each next state.
+    fn get_next_state_probabilities(&self) -> HashMap<S, f64> {
+        self.next_states
+            .iter()
+            .map(|(next_state, count)| {
+                (
+                    next_state.clone(),
+                    f64::from(*count) / f64::from(self.num_visits),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The statistics of the transitions observed by a model-based agent.
+/// A state is known to be terminal once an episode has been done by reaching it.
+#[derive(Debug, Clone)]
+struct EmpiricalModel<S, A>
+where
+    S: State,
+    A: Action,
+{
+    pairs: HashMap<(S, A), PairStatistics<S>>,
+    terminal_states: HashSet<S>,
+}
+
+impl<S, A> EmpiricalModel<S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn new() -> Self {
+        Self {
+            pairs: HashMap::new(),
+            terminal_states: HashSet::new(),
+        }
+    }
+
+    /// Records the given transition and returns the number of visits of its state-action pair.
+    fn record(&mut self, state: &S, action: &A, next_state: &S, reward: f64, done: bool) -> u32 {
+        if done {
+            self.terminal_states.insert(next_state.clone());
+        }
+        let statistics = self
+            .pairs
+            .entry((state.clone(), action.clone()))
+            .or_insert_with(|| PairStatistics {
+                num_visits: 0,
+                total_reward: 0.0,
+                next_states: HashMap::new(),
+            });
+        statistics.num_visits += 1;
+        statistics.total_reward += reward;
+        *statistics
+            .next_states
+            .entry(next_state.clone())
+            .or_default() += 1;
+        statistics.num_visits
+    }
+
+    /// Returns the statistics of the given pair, if it has been visited.
+    fn get(&self, state: &S, action: &A) -> Option<&PairStatistics<S>> {
+        self.pairs.get(&(state.clone(), action.clone()))
+    }
+
+    /// Returns the number of visits of the given pair.
+    fn get_num_visits(&self, state: &S, action: &A) -> u32 {
+        self.get(state, action)
+            .map_or(0, |statistics| statistics.num_visits)
+    }
+
+    fn is_state_terminal(&self, state: &S) -> bool {
+        self.terminal_states.contains(state)
+    }
+}
+
+/// The optimistic MDP planned on by R-max.
+/// A pair visited at least `known_threshold` times leads to its most frequent next state
+/// with its average reward, which is exact when the environment is deterministic.
+/// Any other pair loops on its state with the maximum reward,
+/// so that its value is the highest possible return.
+struct RMaxMDP<'a, S, A>
+where
+    S: State,
+    A: Action,
+{
+    model: &'a EmpiricalModel<S, A>,
+    states: &'a Sampler<S>,
+    actions: &'a Sampler<A>,
+    max_reward: f64,
+    known_threshold: u32,
+}
+
+impl<'a, S, A> RMaxMDP<'a, S, A>
+where
+    S: State,
+    A: Action,
+{
+    fn new(
+        model: &'a EmpiricalModel<S, A>,
+        states: &'a Sampler<S>,
+        actions: &'a Sampler<A>,
+        max_reward: f64,
+        known_threshold: u32,
+    ) -> Self {
+        Self {
+            model,
+            states,
+            actions,
+            max_reward,
+            known_threshold,
+        }
+    }
+
+    /// Returns whether every pair of the non-terminal states is known.
+    fn is_known(&self) -> bool {
+        self.states
+            .iter()
+            .filter(|state| !self.model.is_state_terminal(state))
+            .all(|state| {
+                self.actions
+                    .iter()
+                    .all(|action| self.model.get_num_visits(state, action) >= self.known_threshold)
+            })
+    }
+
+    /// Solves the MDP with dynamic programming and returns the optimal policy.
+    fn plan(&self, config: &Config) -> Policy<S, A> {
+        let state_value = policy_value_iteration(self, config);
+        policy_improvement(self, config, &state_value)
+    }
+}
+
+impl<S, A> MDP for RMaxMDP<'_, S, A>
+where
+    S: State,
+    A: Action,
+{
+    type State = S;
+    type Action = A;
+
+    fn get_states(&self) -> &Sampler<Self::State> {
+        self.states
+    }
+
+    fn get_actions(&self) -> &Sampler<Self::Action> {
+        self.actions
+    }
+
+    fn is_state_terminal(&self, state: &Self::State) -> bool {
+        self.model.is_state_terminal(state)
+    }
+
+    fn transition(&self, state: &Self::State, action: &Self::Action) -> (Self::State, f64) {
+        if self.model.is_state_terminal(state) {
+            return (state.clone(), 0.0);
+        }
+        match self.model.get(state, action) {
+            Some(statistics) if statistics.num_visits >= self.known_threshold => (
+                statistics.get_most_frequent_next_state().clone(),
+                statistics.get_mean_reward(),
+            ),
+            _ => (state.clone(), self.max_reward),
+        }
+    }
+}
+
+/// # R-max
+///
+/// This function implements the R-max algorithm.
+/// A state-action pair is known once it has been visited `known_threshold` times.
+/// The agent plans on a model where known pairs behave as observed
+/// and unknown pairs yield the `max_reward` forever,
+/// with policy or value iteration depending on the `iterations_before_improvement` parameter,
+/// and follows the resulting policy.
+/// It plans again each time a pair becomes known.
+/// The agent plays at most the `num_episodes` parameter in the config,
+/// each of at most the `max_num_steps` parameter in the config,
+/// and stops as soon as every pair of the non-terminal states is known.
+/// The `max_reward` must bound the rewards of the environment from above
+/// and the discount factor must be lower than one.
+/// Returns the learned policy along with the number of samples used.
+pub fn r_max<E>(
+    env: &mut E,
+    states: &Sampler<E::State>,
+    config: &Config,
+    max_reward: f64,
+    known_threshold: u32,
+) -> (Policy<E::State, E::Action>, u32)
+where
+    E: Environment,
+{
+    assert!(
+        config.discount_factor < 1.0,
+        "discount factor must be lower than one for R-max."
+    );
+    assert!(known_threshold > 0, "known threshold must be positive.");
+    let mut model = EmpiricalModel::new();
+    let mut num_samples = 0;
+    let mut policy = RMaxMDP::new(
+        &model,
+        states,
+        env.get_actions(),
+        max_reward,
+        known_threshold,
+    )
+    .plan(config);
+    'episodes: for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = policy.get(&state).clone();
+            let (next_state, reward, done, truncated) = env.step(&action);
+            num_samples += 1;
+            let num_visits = model.record(&state, &action, &next_state, reward, done);
+            if num_visits == known_threshold {
+                let mdp = RMaxMDP::new(
+                    &model,
+                    states,
+                    env.get_actions(),
+                    max_reward,
+                    known_threshold,
+                );
+                policy = mdp.plan(config);
+                if mdp.is_known() {
+                    break 'episodes;
+                }
+            }
+            if done || truncated {
+                break;
+            }
+            state = next_state;
+        }
+    }
+    (policy, num_samples)
+}
+
+/// The confidence bounds of UCRL2 after the given number of samples.
+struct ConfidenceBounds {
+    num_states: f64,
+    reward_range: f64,
+    reward_log: f64,
+    transition_log: f64,
+}
+
+impl ConfidenceBounds {
+    fn new(
+        num_states: usize,
+        num_actions: usize,
+        num_samples: u32,
+        reward_range: f64,
+        delta: f64,
+    ) -> Self {
+        let num_states = num_states as f64;
+        let num_actions = num_actions as f64;
+        let num_samples = f64::from(num_samples.max(1));
+        Self {
+            num_states,
+            reward_range,
+            reward_log: (2.0 * num_states * num_actions * num_samples / delta).ln(),
+            transition_log: (2.0 * num_actions * num_samples / delta).ln(),
+        }
+    }
+
+    /// Returns the radius of the confidence interval of the mean reward of a pair.
+    fn get_reward_radius(&self, num_visits: u32) -> f64 {
+        self.reward_range * (7.0 * self.reward_log / (2.0 * f64::from(num_visits))).sqrt()
+    }
+
+    /// Returns the radius of the L1 ball around the observed next state distribution of a pair.
+    fn get_transition_radius(&self, num_visits: u32) -> f64 {
+        (14.0 * self.num_states * self.transition_log / f64::from(num_visits)).sqrt()
+    }
+}
+
+/// Returns the highest expected value of the next state among the distributions
+/// within the given L1 distance of the observed ones:
+/// the probability of the best state is increased by half the radius
+/// and the excess is removed from the worst states first.
+fn get_optimistic_next_value<S>(
+    mut probabilities: HashMap<S, f64>,
+    state_value: &StateValue<S>,
+    best_state: &S,
+    radius: f64,
+) -> f64
+where
+    S: State,
+{
+    let best_probability = probabilities.entry(best_state.clone()).or_default();
+    *best_probability = (*best_probability + radius / 2.0).min(1.0);
+    let mut support: Vec<(S, f64)> = probabilities.into_iter().collect();
+    support.sort_by(|(a, _), (b, _)| state_value.get(a).total_cmp(&state_value.get(b)));
+    let mut excess = support.iter().map(|(_, p)| p).sum::<f64>() - 1.0;
+    for (state, probability) in support.iter_mut() {
+        if excess <= 0.0 {
+            break;
+        }
+        if state == best_state {
+            continue;
+        }
+        let removed = probability.min(excess);
+        *probability -= removed;
+        excess -= removed;
+    }
+    support
+        .iter()
+        .map(|(state, probability)| probability * state_value.get(state))
+        .sum()
+}
+
+/// Runs extended value iteration on the plausible MDPs given by the confidence bounds
+/// and returns the policy that is greedy with respect to the optimistic values.
+/// An unvisited pair yields the maximum reward and leads to the best state.
+fn extended_value_iteration<S, A>(
+    model: &EmpiricalModel<S, A>,
+    states: &Sampler<S>,
+    actions: &Sampler<A>,
+    config: &Config,
+    bounds: &ConfidenceBounds,
+    max_reward: f64,
+) -> Policy<S, A>
+where
+    S: State,
+    A: Action,
+{
+    let get_best_state = |state_value: &StateValue<S>| {
+        states
+            .iter()
+            .reduce(|best, other| {
+                if state_value.get(other) > state_value.get(best) {
+                    other
+                } else {
+                    best
+                }
+            })
+            .expect("states must not be empty.")
+            .clone()
+    };
+    let get_action_value =
+        |state: &S, action: &A, state_value: &StateValue<S>, best_state: &S| match model
+            .get(state, action)
+        {
+            Some(statistics) => {
+                let reward = (statistics.get_mean_reward()
+                    + bounds.get_reward_radius(statistics.num_visits))
+                .min(max_reward);
+                let next_value = get_optimistic_next_value(
+                    statistics.get_next_state_probabilities(),
+                    state_value,
+                    best_state,
+                    bounds.get_transition_radius(statistics.num_visits),
+                );
+                reward + config.discount_factor * next_value
+            }
+            None => max_reward + config.discount_factor * state_value.get(best_state),
+        };
+    let mut state_value = StateValue::new(states);
+    for _ in 0..config.max_num_iterations {
+        let best_state = get_best_state(&state_value);
+        let mut delta: f64 = 0.0;
+        for state in states
+            .iter()
+            .filter(|state| !model.is_state_terminal(state))
+        {
+            let new_state_value = actions
+                .iter()
+                .map(|action| get_action_value(state, action, &state_value, &best_state))
+                .fold(f64::NEG_INFINITY, f64::max);
+            delta = delta.max((new_state_value - state_value.get(state)).abs());
+            state_value.insert(state, new_state_value);
+        }
+        if delta < config.tolerance {
+            break;
+        }
+    }
+    let best_state = get_best_state(&state_value);
+    let mut policy = Policy::new(states, actions);
+    for state in states {
+        // ties are frequent while the confidence intervals are wide,
+        // so they are broken in favor of the least visited action
+        let (best_action, _, _) = actions
+            .iter()
+            .map(|action| {
+                (
+                    action,
+                    get_action_value(state, action, &state_value, &best_state),
+                    model.get_num_visits(state, action),
+                )
+            })
+            .reduce(|best, other| {
+                if other.1 > best.1 || (other.1 == best.1 && other.2 < best.2) {
+                    other
+                } else {
+                    best
+                }
+            })
+            .expect("actions must not be empty.");
+        policy.insert(state, best_action);
+    }
+    policy
+}
+
+/// # UCRL2
+///
+/// This function implements the UCRL2 algorithm with discounted returns.
+/// The agent keeps confidence intervals around the observed mean rewards
+/// and next state distributions of each state-action pair,
+/// whose widths depend on the `delta` confidence parameter.
+/// At the start of each phase, it follows the policy computed by extended value iteration,
+/// which picks the most optimistic rewards and transitions within the confidence intervals.
+/// Ties between actions are broken in favor of the least visited one.
+/// A phase ends when the visits of a pair during the phase
+/// reach its number of visits before the phase.
+/// The extended value iteration stops when no value changes by more than the `tolerance` parameter,
+/// or after the `max_num_iterations` parameter in the config.
+/// The agent plays the `num_episodes` parameter in the config,
+/// each of at most the `max_num_steps` parameter in the config.
+/// The rewards of the environment must lie between `min_reward` and `max_reward`
+/// and the discount factor must be lower than one.
+/// Returns the last policy computed by extended value iteration,
+/// which is computed again on the final model if the last sample ended a phase,
+/// along with the number of samples used.
+/// This policy stays optimistic, so it keeps exploring
+/// until the confidence intervals are narrow compared to the range of the rewards.
+pub fn ucrl2<E>(
+    env: &mut E,
+    states: &Sampler<E::State>,
+    config: &Config,
+    min_reward: f64,
+    max_reward: f64,
+    delta: f64,
+) -> (Policy<E::State, E::Action>, u32)
+where
+    E: Environment,
+{
+    assert!(
+        config.discount_factor < 1.0,
+        "discount factor must be lower than one for UCRL2."
+    );
+    assert!(
+        min_reward <= max_reward,
+        "min reward must not be greater than max reward."
+    );
+    assert!(
+        delta > 0.0 && delta < 1.0,
+        "delta must be between zero and one."
+    );
+    let num_states = states.iter().count();
+    let num_actions = env.get_actions().iter().count();
+    let mut model = EmpiricalModel::new();
+    let mut num_samples = 0;
+    let mut phase_visits: HashMap<(E::State, E::Action), u32> = HashMap::new();
+    let plan = |model: &EmpiricalModel<E::State, E::Action>,
+                actions: &Sampler<E::Action>,
+                num_samples: u32| {
+        let bounds = ConfidenceBounds::new(
+            num_states,
+            num_actions,
+            num_samples,
+            max_reward - min_reward,
+            delta,
+        );
+        extended_value_iteration(model, states, actions, config, &bounds, max_reward)
+    };
+    let mut policy = None;
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let current_policy =
+                policy.get_or_insert_with(|| plan(&model, env.get_actions(), num_samples));
+            let action = current_policy.get(&state).clone();
+            let (next_state, reward, done, truncated) = env.step(&action);
+            num_samples += 1;
+            let num_visits = model.record(&state, &action, &next_state, reward, done);
+            let num_phase_visits = phase_visits
+                .entry((state, action))
+                .and_modify(|visits| *visits += 1)
+                .or_insert(1);
+            if *num_phase_visits >= (num_visits - *num_phase_visits).max(1) {
+                // start a new phase
+                phase_visits.clear();
+                policy = None;
+            }
+            if done || truncated {
+                break;
+            }
+            state = next_state;
+        }
+    }
+    let policy = policy.unwrap_or_else(|| plan(&model, env.get_actions(), num_samples));
+    (policy, num_samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        assert_policy_optimal, get_gridworld, get_maze, get_test_config, GridworldAction,
+        GridworldState,
+    };
+    use crate::models::MDPEnvironment;
+    use crate::solvers::stochastic_shortest_path::ssp_policy_evaluation;
+
+    #[test]
+    fn test_r_max() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, num_samples) = r_max(&mut env, mdp.get_states(), &config, 100.0, 1);
+        assert_policy_optimal(&policy);
+        // each of the two non-terminal states has four actions to try
+        assert!(num_samples >= 8);
+        // it stops as soon as every pair is known, long before the end of the budget
+        assert!(num_samples < config.num_episodes);
+    }
+
+    #[test]
+    fn test_r_max_on_maze() {
+        let mdp = get_maze();
+        let config = get_test_config();
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, num_samples) = r_max(&mut env, mdp.get_states(), &config, 100.0, 2);
+        // the shortest path takes six steps, the last one to the end
+        let state_value = ssp_policy_evaluation(&mdp, &policy).unwrap();
+        assert_eq!(state_value.get(&GridworldState::new(0, 0)), 95.0);
+        // each of the eleven non-terminal states has four actions to try twice
+        assert!(num_samples >= 88);
+    }
+
+    /// A single decision between a left arm giving 0 and a right arm giving 1.
+    struct TwoArms {
+        actions: Sampler<GridworldAction>,
+    }
+
+    impl Environment for TwoArms {
+        type State = GridworldState;
+        type Action = GridworldAction;
+
+        fn get_actions(&self) -> &Sampler<Self::Action> {
+            &self.actions
+        }
+
+        fn reset(&mut self) -> Self::State {
+            GridworldState::new(0, 0)
+        }
+
+        fn step(&mut self, action: &Self::Action) -> (Self::State, f64, bool, bool) {
+            let reward = if *action == GridworldAction::Right {
+                1.0
+            } else {
+                0.0
+            };
+            (GridworldState::new(0, 1), reward, true, false)
+        }
+    }
+
+    #[test]
+    fn test_ucrl2() {
+        let config = get_test_config().num_episodes(1_000);
+        let mut env = TwoArms {
+            actions: Sampler::new(vec![GridworldAction::Left, GridworldAction::Right]),
+        };
+        let states = Sampler::new(vec![GridworldState::new(0, 0), GridworldState::new(0, 1)]);
+        let (policy, num_samples) = ucrl2(&mut env, &states, &config, 0.0, 1.0, 0.1);
+        assert_eq!(
+            policy.get(&GridworldState::new(0, 0)),
+            &GridworldAction::Right
+        );
+        // each episode is a single step
+        assert_eq!(num_samples, config.num_episodes);
+    }
+
+    #[test]
+    fn test_ucrl2_on_gridworld() {
+        // a single start state keeps the runs identical,
+        // and a lower discount factor lets the confidence intervals narrow in time
+        let mdp = get_gridworld().initial_states(vec![GridworldState::new(0, 0)]);
+        let config = get_test_config()
+            .discount_factor(0.5)
+            .num_episodes(1_000)
+            .max_num_steps(20);
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, _) = ucrl2(&mut env, mdp.get_states(), &config, -1.0, 100.0, 0.1);
+        assert_policy_optimal(&policy);
+    }
+}
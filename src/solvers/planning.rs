@@ -0,0 +1,190 @@
+//! # planning
+//!
+//! The `planning` module contains the implementations of the online planning algorithms.
+//! Instead of solving the whole MDP, they plan from the current state
+//! by simulating transitions with [`MDP::transition`] and [`MDP::is_state_terminal`],
+//! so that they never enumerate the states with [`MDP::get_states`].
+//! This makes them suitable for MDPs with too many states to be solved offline.
+
+use rand::prelude::*;
+
+use crate::models::{Action, Config, SearchNode, SearchTree, MDP};
+
+/// Returns the action of the node maximizing the UCB1 score
+/// `mean + c * sqrt(ln(N) / n)`.
+/// Actions that have never been selected are tried first, in a random order.
+fn select_action<A>(node: &SearchNode<A>, exploration_constant: f64) -> A
+where
+    A: Action,
+{
+    let untried: Vec<&A> = node
+        .iter()
+        .filter(|(_, statistics)| statistics.num_visits == 0)
+        .map(|(action, _)| action)
+        .collect();
+    if let Some(action) = untried.choose(&mut thread_rng()) {
+        return (*action).clone();
+    }
+    let ln_visits = f64::from(node.get_num_visits()).ln();
+    let (best_action, _) = node
+        .iter()
+        .map(|(action, statistics)| {
+            let bonus = (ln_visits / f64::from(statistics.num_visits)).sqrt();
+            (
+                action,
+                statistics.get_mean_return() + exploration_constant * bonus,
+            )
+        })
+        .reduce(|best, other| if other.1 > best.1 { other } else { best })
+        .expect("search node must contain at least one action.");
+    best_action.clone()
+}
+
+/// Plays the rollout policy from the given state for at most the given number of steps
+/// and returns the discounted return.
+fn rollout<M, R>(
+    mdp: &M,
+    config: &Config,
+    rollout_policy: &mut R,
+    mut state: M::State,
+    num_steps: u32,
+) -> f64
+where
+    M: MDP,
+    R: FnMut(&M::State) -> M::Action,
+{
+    let mut return_ = 0.0;
+    let mut discount = 1.0;
+    for _ in 0..num_steps {
+        if mdp.is_state_terminal(&state) {
+            break;
+        }
+        let action = rollout_policy(&state);
+        let (next_state, reward) = mdp.transition(&state, &action);
+        return_ += discount * reward;
+        discount *= config.discount_factor;
+        state = next_state;
+    }
+    return_
+}
+
+/// # Upper Confidence Bounds Applied to Trees
+///
+/// This function implements the UCT algorithm,
+/// a Monte Carlo tree search planner which selects actions with UCB1.
+/// Each simulation starts from the given state and has four phases:
+/// - selection: in the states already in the tree, the action maximizing
+///   `mean + c * sqrt(ln(N) / n)` is taken, where `c` is the `exploration_constant` parameter
+///   in the config and actions never taken are tried first,
+/// - expansion: the first state reached outside the tree is added to it,
+/// - simulation: the given rollout policy is played from that state,
+/// - backpropagation: the discounted return is recorded for each state-action pair of the path.
+///
+/// A simulation ends in a terminal state
+/// or after the `max_num_steps` parameter in the config, rollout included.
+/// The number of simulations is the `num_simulations` parameter in the config.
+/// The default exploration constant suits returns between zero and one,
+/// so it should be scaled with the range of the returns of the MDP.
+/// Returns the most visited action of the given state along with the search tree.
+pub fn uct<M, R>(
+    mdp: &M,
+    config: &Config,
+    state: &M::State,
+    mut rollout_policy: R,
+) -> (M::Action, SearchTree<M::State, M::Action>)
+where
+    M: MDP,
+    R: FnMut(&M::State) -> M::Action,
+{
+    let mut tree = SearchTree::new();
+    tree.expand(state, mdp.get_actions());
+    for _ in 0..config.num_simulations {
+        let mut path = vec![];
+        let mut current = state.clone();
+        let mut leaf_value = 0.0;
+        let mut depth = 0;
+        while depth < config.max_num_steps && !mdp.is_state_terminal(&current) {
+            let Some(node) = tree.get(&current) else {
+                // expand and simulate
+                tree.expand(&current, mdp.get_actions());
+                leaf_value = rollout(
+                    mdp,
+                    config,
+                    &mut rollout_policy,
+                    current,
+                    config.max_num_steps - depth,
+                );
+                break;
+            };
+            let action = select_action(node, config.exploration_constant);
+            let (next_state, reward) = mdp.transition(&current, &action);
+            path.push((current, action, reward));
+            current = next_state;
+            depth += 1;
+        }
+        // backpropagate
+        let mut return_ = leaf_value;
+        for (state, action, reward) in path.into_iter().rev() {
+            return_ = reward + config.discount_factor * return_;
+            // unwrap is safe because the states of the path are in the tree
+            tree.get_mut(&state).unwrap().update(&action, return_);
+        }
+    }
+    // unwrap is safe because the root is in the tree
+    let best_action = tree.get(state).unwrap().most_visited().clone();
+    (best_action, tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        get_gridworld, get_optimal_policy, get_test_config, Cell, Gridworld, GridworldAction,
+        GridworldState,
+    };
+
+    #[test]
+    fn test_uct() {
+        let mdp = get_gridworld();
+        let config = get_test_config()
+            .num_simulations(200)
+            .max_num_steps(20)
+            .exploration_constant(50.0);
+        let optimal_policy = get_optimal_policy(mdp.get_states(), mdp.get_actions());
+        for state in [GridworldState::new(0, 0), GridworldState::new(0, 1)] {
+            let (action, tree) = uct(&mdp, &config, &state, |_| {
+                mdp.get_actions().get_random().clone()
+            });
+            assert_eq!(&action, optimal_policy.get(&state));
+            // states reached again along a path are visited several times per simulation
+            assert!(tree.get(&state).unwrap().get_num_visits() >= 200);
+        }
+    }
+
+    #[test]
+    fn test_uct_long_corridor() {
+        // the rollout policy never reaches the end of the corridor,
+        // so that its reward can only be found by growing the tree
+        let length = 10;
+        let mut cells: Vec<Cell> = (0..length).map(|_| Cell::Air).collect();
+        cells[length - 1] = Cell::End;
+        let states = (0..length).map(|j| GridworldState::new(0, j)).collect();
+        let actions = vec![GridworldAction::Left, GridworldAction::Right];
+        let mdp = Gridworld::new(vec![cells], states, actions);
+        let config = get_test_config()
+            .num_simulations(1_000)
+            .max_num_steps(50)
+            .exploration_constant(50.0);
+        let mut state = GridworldState::new(0, 0);
+        let (action, _) = uct(&mdp, &config, &state, |_| GridworldAction::Left);
+        assert_eq!(action, GridworldAction::Right);
+        // planning from each state reaches the end
+        let mut num_steps = 0;
+        while !mdp.is_state_terminal(&state) && num_steps < 2 * length {
+            let (action, _) = uct(&mdp, &config, &state, |_| GridworldAction::Left);
+            state = mdp.transition(&state, &action).0;
+            num_steps += 1;
+        }
+        assert!(mdp.is_state_terminal(&state));
+    }
+}
@@ -0,0 +1,361 @@
+//! # policy_gradient
+//!
+//! The `policy_gradient` module contains the implementations of the tabular policy gradient algorithms.
+//! Instead of learning action values and acting greedily with respect to them,
+//! the solvers directly learn a stochastic policy.
+//! The policy is parameterised by action preferences,
+//! the probability of each action being the softmax of the preferences of the state.
+//! The preferences are updated by gradient ascent with the `learning_rate` parameter in the config
+//! and the critics, when there is one, with the `secondary_learning_rate` parameter.
+//! The solvers only interact with the problem through the [`Environment`] trait.
+//! The states do not need to be known in advance:
+//! the preferences and the critic of a state are created the first time it is visited,
+//! with every preference and value at zero,
+//! and the returned policy is defined on the visited states, including the ones episodes end in.
+
+use std::collections::HashMap;
+
+use crate::models::{
+    Action, ActionValue, Config, Environment, Episode, PolicyAndValue, Sampler, State, StateValue,
+    StochasticPolicy, Transition,
+};
+
+/// Samples an action from the softmax of the preferences of the given state,
+/// which are created with the given actions if the state has not been visited yet.
+fn sample_action<S, A>(preferences: &mut ActionValue<S, A>, actions: &Sampler<A>, state: &S) -> A
+where
+    S: State,
+    A: Action,
+{
+    preferences
+        .get_or_insert_state_action_value(state, actions, 0.0)
+        .softmax(1.0)
+        .clone()
+}
+
+/// Returns the gradient of the log-probability of the given action
+/// with respect to the preferences of the given state,
+/// which is one minus the probability for the action itself
+/// and minus the probability for the other actions.
+fn get_log_probability_gradient<S, A>(
+    preferences: &ActionValue<S, A>,
+    state: &S,
+    action: &A,
+) -> HashMap<A, f64>
+where
+    S: State,
+    A: Action,
+{
+    preferences
+        .get_state_action_value(state)
+        .softmax_probabilities(1.0)
+        .into_iter()
+        .map(|(other, probability)| {
+            let indicator = if &other == action { 1.0 } else { 0.0 };
+            (other, indicator - probability)
+        })
+        .collect()
+}
+
+/// Moves the preferences of the given state by the given step
+/// along the gradient of the log-probability of the given action.
+fn update_preferences<S, A>(preferences: &mut ActionValue<S, A>, state: &S, action: &A, step: f64)
+where
+    S: State,
+    A: Action,
+{
+    for (other, gradient) in get_log_probability_gradient(preferences, state, action) {
+        let preference = preferences.get(state, &other);
+        preferences.insert(state, &other, preference + step * gradient);
+    }
+}
+
+/// Plays an episode in the environment by sampling actions from the preferences.
+fn generate_episode<E>(
+    env: &mut E,
+    config: &Config,
+    preferences: &mut ActionValue<E::State, E::Action>,
+) -> Episode<E::State, E::Action>
+where
+    E: Environment,
+{
+    let mut episode = Episode::new();
+    let mut state = env.reset();
+    for _ in 0..config.max_num_steps {
+        let action = sample_action(preferences, env.get_actions(), &state);
+        let (next_state, reward, done, truncated) = env.step(&action);
+        preferences.get_or_insert_state_action_value(&next_state, env.get_actions(), 0.0);
+        episode.push(Transition {
+            state,
+            action,
+            reward,
+            next_state: next_state.clone(),
+            done,
+        });
+        state = next_state;
+        if done || truncated {
+            break;
+        }
+    }
+    episode
+}
+
+fn reinforce_baseline<E>(
+    env: &mut E,
+    config: &Config,
+    baseline: bool,
+) -> PolicyAndValue<E::State, E::Action>
+where
+    E: Environment,
+{
+    let mut preferences = ActionValue::empty();
+    let mut state_value = StateValue::empty();
+    for _ in 0..config.num_episodes {
+        let episode = generate_episode(env, config, &mut preferences);
+        // compute the return following each step
+        let mut returns: Vec<f64> = episode
+            .iter()
+            .rev()
+            .scan(0.0, |return_, transition| {
+                *return_ = transition.reward + config.discount_factor * *return_;
+                Some(*return_)
+            })
+            .collect();
+        returns.reverse();
+        let mut discount = 1.0;
+        for (transition, return_) in episode.iter().zip(returns) {
+            let Transition { state, action, .. } = transition;
+            let advantage = if baseline {
+                let value = state_value.get_or_insert(state, 0.0);
+                state_value.insert(
+                    state,
+                    value + config.secondary_learning_rate * (return_ - value),
+                );
+                return_ - value
+            } else {
+                return_
+            };
+            update_preferences(
+                &mut preferences,
+                state,
+                action,
+                config.learning_rate * discount * advantage,
+            );
+            discount *= config.discount_factor;
+        }
+    }
+    let states = Sampler::new(preferences.get_states());
+    let policy = preferences.boltzmann_policy(&states, env.get_actions(), 1.0);
+    (policy, state_value)
+}
+
+/// # REINFORCE
+///
+/// This function implements the REINFORCE algorithm, or Monte Carlo policy gradient.
+/// After each episode, the preferences of each visited state are moved
+/// along the gradient of the log-probability of the action taken,
+/// scaled by the discounted return that followed.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+pub fn reinforce<E>(env: &mut E, config: &Config) -> StochasticPolicy<E::State, E::Action>
+where
+    E: Environment,
+{
+    let (policy, _) = reinforce_baseline(env, config, false);
+    policy
+}
+
+/// # REINFORCE with Baseline
+///
+/// This function implements the REINFORCE algorithm with a state value baseline.
+/// It works like [`reinforce`], except that the return is replaced by its difference
+/// with the estimated value of the state, which reduces the variance of the updates.
+/// The state value is learned from the returns
+/// and is returned along with the policy.
+pub fn reinforce_with_baseline<E>(
+    env: &mut E,
+    config: &Config,
+) -> PolicyAndValue<E::State, E::Action>
+where
+    E: Environment,
+{
+    reinforce_baseline(env, config, true)
+}
+
+fn actor_critic<E>(
+    env: &mut E,
+    config: &Config,
+    trace_decay: f64,
+) -> PolicyAndValue<E::State, E::Action>
+where
+    E: Environment,
+{
+    let mut preferences = ActionValue::empty();
+    let mut state_value = StateValue::empty();
+    for _ in 0..config.num_episodes {
+        let mut critic_traces: HashMap<E::State, f64> = HashMap::new();
+        let mut actor_traces: HashMap<E::State, HashMap<E::Action, f64>> = HashMap::new();
+        let mut discount = 1.0;
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = sample_action(&mut preferences, env.get_actions(), &state);
+            let (next_state, reward, done, truncated) = env.step(&action);
+            preferences.get_or_insert_state_action_value(&next_state, env.get_actions(), 0.0);
+            let next_value = if done {
+                0.0
+            } else {
+                state_value.get_or_insert(&next_state, 0.0)
+            };
+            let td_error = reward + config.discount_factor * next_value
+                - state_value.get_or_insert(&state, 0.0);
+            // decay the traces and accumulate the gradients of the current state
+            let decay = config.discount_factor * trace_decay;
+            for trace in critic_traces.values_mut() {
+                *trace *= decay;
+            }
+            for trace in actor_traces
+                .values_mut()
+                .flat_map(|traces| traces.values_mut())
+            {
+                *trace *= decay;
+            }
+            *critic_traces.entry(state.clone()).or_insert(0.0) += 1.0;
+            let state_actor_traces = actor_traces.entry(state.clone()).or_default();
+            for (other, gradient) in get_log_probability_gradient(&preferences, &state, &action) {
+                *state_actor_traces.entry(other).or_insert(0.0) += discount * gradient;
+            }
+            // update critic and actor
+            for (traced_state, trace) in &critic_traces {
+                let value = state_value.get(traced_state);
+                state_value.insert(
+                    traced_state,
+                    value + config.secondary_learning_rate * td_error * trace,
+                );
+            }
+            for (traced_state, traces) in &actor_traces {
+                for (traced_action, trace) in traces {
+                    let preference = preferences.get(traced_state, traced_action);
+                    preferences.insert(
+                        traced_state,
+                        traced_action,
+                        preference + config.learning_rate * td_error * trace,
+                    );
+                }
+            }
+            discount *= config.discount_factor;
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    let states = Sampler::new(preferences.get_states());
+    let policy = preferences.boltzmann_policy(&states, env.get_actions(), 1.0);
+    (policy, state_value)
+}
+
+/// # One-Step Actor-Critic
+///
+/// This function implements the one-step actor-critic algorithm.
+/// At each step, the critic, a state value, is updated with the TD(0) rule
+/// and the actor, the policy, is moved along the gradient of the log-probability
+/// of the action taken, scaled by the temporal difference error of the critic.
+/// Unlike REINFORCE, it learns during the episode
+/// and bootstraps from the critic instead of waiting for the return.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+pub fn one_step_actor_critic<E>(env: &mut E, config: &Config) -> PolicyAndValue<E::State, E::Action>
+where
+    E: Environment,
+{
+    actor_critic(env, config, 0.0)
+}
+
+/// # Actor-Critic with Eligibility Traces
+///
+/// This function implements the actor-critic algorithm with eligibility traces,
+/// the online equivalent of updating the actor and the critic towards the λ-return.
+/// It works like [`one_step_actor_critic`],
+/// except that the temporal difference error of each step
+/// also updates the previously visited states and actions,
+/// weighted by traces decaying with the `trace_decay` parameter in the config.
+/// A decay of zero gives the one-step actor-critic
+/// and a decay of one approaches REINFORCE with baseline.
+pub fn actor_critic_lambda<E>(env: &mut E, config: &Config) -> PolicyAndValue<E::State, E::Action>
+where
+    E: Environment,
+{
+    actor_critic(env, config, config.trace_decay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        assert_policy_optimal, get_gridworld, get_test_config, GridworldAction, GridworldState,
+    };
+    use crate::models::{MDPEnvironment, MDP};
+
+    fn get_config() -> Config {
+        get_test_config()
+            .learning_rate(0.01)
+            .secondary_learning_rate(0.1)
+            .num_episodes(300)
+    }
+
+    fn assert_stochastic_policy_optimal(
+        policy: &StochasticPolicy<GridworldState, GridworldAction>,
+    ) {
+        let mdp = get_gridworld();
+        let policy = policy.most_likely_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    fn assert_critic_positive(state_value: &StateValue<GridworldState>) {
+        let mdp = get_gridworld();
+        for state in mdp.get_states() {
+            if !mdp.is_state_terminal(state) {
+                assert!(state_value.get(state) > 50.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reinforce() {
+        let mdp = get_gridworld();
+        let mut env = MDPEnvironment::new(&mdp);
+        let policy = reinforce(&mut env, &get_config());
+        assert_stochastic_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_reinforce_with_baseline() {
+        let mdp = get_gridworld();
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, state_value) = reinforce_with_baseline(&mut env, &get_config());
+        assert_stochastic_policy_optimal(&policy);
+        assert_critic_positive(&state_value);
+    }
+
+    #[test]
+    fn test_one_step_actor_critic() {
+        let mdp = get_gridworld();
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, state_value) = one_step_actor_critic(&mut env, &get_config());
+        assert_stochastic_policy_optimal(&policy);
+        assert_critic_positive(&state_value);
+    }
+
+    #[test]
+    fn test_actor_critic_lambda() {
+        let mdp = get_gridworld();
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, state_value) = actor_critic_lambda(&mut env, &get_config());
+        assert_stochastic_policy_optimal(&policy);
+        assert_critic_positive(&state_value);
+    }
+}
@@ -0,0 +1,230 @@
+//! # stochastic_shortest_path
+//!
+//! The `stochastic_shortest_path` module contains the implementations
+//! of the stochastic shortest path algorithms.
+//! A stochastic shortest path problem is an undiscounted MDP whose episodes end in a terminal state,
+//! where the rewards are usually negative costs, as in navigation tasks.
+//! A policy is proper when it reaches a terminal state from every state, and improper otherwise.
+//! Without discounting, the return of an improper policy may be infinite,
+//! so that the dynamic programming solvers with a discount factor of one may never converge.
+//! The solvers of this module ignore the discount factor,
+//! always terminate and return an [`SSPError`] when the problem has no solution.
+
+use std::collections::HashSet;
+
+use crate::errors::SSPError;
+use crate::models::{Policy, StateValue, MDP};
+
+/// An optimal policy of a stochastic shortest path problem along with its state value.
+pub type SSPSolution<S, A> = (Policy<S, A>, StateValue<S>);
+
+/// Follows the policy from the given state
+/// and returns the sum of the rewards until a terminal state,
+/// or `None` if the policy enters a cycle before reaching one.
+fn get_policy_return<M>(
+    mdp: &M,
+    policy: &Policy<M::State, M::Action>,
+    state: &M::State,
+) -> Option<f64>
+where
+    M: MDP,
+{
+    let mut return_ = 0.0;
+    let mut seen = HashSet::new();
+    let mut current = state.clone();
+    while !mdp.is_state_terminal(&current) {
+        if !seen.insert(current.clone()) {
+            return None;
+        }
+        let (next_state, reward) = mdp.transition(&current, policy.get(&current));
+        return_ += reward;
+        current = next_state;
+    }
+    Some(return_)
+}
+
+/// Returns the states from which the policy does not reach a terminal state.
+pub fn get_improper_states<M>(mdp: &M, policy: &Policy<M::State, M::Action>) -> Vec<M::State>
+where
+    M: MDP,
+{
+    mdp.get_states()
+        .iter()
+        .filter(|state| get_policy_return(mdp, policy, state).is_none())
+        .cloned()
+        .collect()
+}
+
+/// Returns whether the policy reaches a terminal state from every state.
+pub fn is_policy_proper<M>(mdp: &M, policy: &Policy<M::State, M::Action>) -> bool
+where
+    M: MDP,
+{
+    get_improper_states(mdp, policy).is_empty()
+}
+
+/// # SSP Policy Evaluation
+///
+/// This function evaluates a policy of a stochastic shortest path problem.
+/// The value of a state is the undiscounted sum of the rewards
+/// obtained by following the policy from it until a terminal state,
+/// which is computed exactly instead of iteratively.
+/// Returns [`SSPError::ImproperPolicy`] if the policy is not proper.
+pub fn ssp_policy_evaluation<M>(
+    mdp: &M,
+    policy: &Policy<M::State, M::Action>,
+) -> Result<StateValue<M::State>, SSPError>
+where
+    M: MDP,
+{
+    let states = mdp.get_states();
+    let mut state_value = StateValue::new(states);
+    for state in states {
+        let return_ = get_policy_return(mdp, policy, state).ok_or(SSPError::ImproperPolicy)?;
+        state_value.insert(state, return_);
+    }
+    Ok(state_value)
+}
+
+/// # SSP Value Iteration
+///
+/// This function implements value iteration for stochastic shortest path problems,
+/// in the manner of the Bellman-Ford algorithm.
+/// The values of the terminal states are zero and the others start at minus infinity,
+/// so that after `k` iterations the value of a state is the highest return
+/// of the paths of at most `k` steps from it to a terminal state.
+/// The action of a state only changes when its value strictly increases,
+/// so that the returned policy is proper even when some cycles have a reward of zero.
+/// The algorithm stops when no value changes, which takes at most as many iterations as states.
+/// Returns [`SSPError::NoProperPolicy`] if a terminal state cannot be reached from some state
+/// and [`SSPError::UnboundedReturn`] if the values still change after that many iterations,
+/// because of a cycle with a positive reward.
+pub fn ssp_value_iteration<M>(mdp: &M) -> Result<SSPSolution<M::State, M::Action>, SSPError>
+where
+    M: MDP,
+{
+    let states = mdp.get_states();
+    let actions = mdp.get_actions();
+    let mut policy = Policy::new(states, actions);
+    let mut state_value = StateValue::new(states);
+    for state in states {
+        if !mdp.is_state_terminal(state) {
+            state_value.insert(state, f64::NEG_INFINITY);
+        }
+    }
+    let num_states = states.iter().count();
+    let mut converged = false;
+    for _ in 0..=num_states {
+        let mut changed = false;
+        for state in states.iter().filter(|state| !mdp.is_state_terminal(state)) {
+            for action in actions {
+                let (next_state, reward) = mdp.transition(state, action);
+                let value = reward + state_value.get(&next_state);
+                if value > state_value.get(state) {
+                    state_value.insert(state, value);
+                    policy.insert(state, action);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(SSPError::UnboundedReturn);
+    }
+    if states
+        .iter()
+        .any(|state| state_value.get(state) == f64::NEG_INFINITY)
+    {
+        return Err(SSPError::NoProperPolicy);
+    }
+    Ok((policy, state_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::environments::gridworld::{
+        assert_policy_optimal, get_gridworld, get_maze, Cell, Gridworld, GridworldAction,
+        GridworldState,
+    };
+    use crate::models::{LinearRewardMDP, OneHot, StateFeatures};
+
+    fn get_policy(
+        mdp: &Gridworld,
+        action: GridworldAction,
+    ) -> Policy<GridworldState, GridworldAction> {
+        let mut policy = Policy::new(mdp.get_states(), mdp.get_actions());
+        for state in mdp.get_states() {
+            policy.insert(state, &action);
+        }
+        policy
+    }
+
+    #[test]
+    fn test_ssp_value_iteration() {
+        let mdp = get_gridworld();
+        let (policy, state_value) = ssp_value_iteration(&mdp).unwrap();
+        assert_policy_optimal(&policy);
+        assert!(is_policy_proper(&mdp, &policy));
+        assert_eq!(state_value.get(&GridworldState::new(0, 0)), 99.0);
+        assert_eq!(
+            ssp_policy_evaluation(&mdp, &policy)
+                .unwrap()
+                .get(&GridworldState::new(0, 0)),
+            99.0
+        );
+    }
+
+    #[test]
+    fn test_ssp_value_iteration_on_maze() {
+        let mdp = get_maze();
+        let (policy, state_value) = ssp_value_iteration(&mdp).unwrap();
+        assert!(is_policy_proper(&mdp, &policy));
+        // the shortest path takes six steps, the last one to the end
+        assert_eq!(state_value.get(&GridworldState::new(0, 0)), 95.0);
+    }
+
+    #[test]
+    fn improper_policy() {
+        let mdp = get_gridworld();
+        let policy = get_policy(&mdp, GridworldAction::Left);
+        assert!(!is_policy_proper(&mdp, &policy));
+        assert_eq!(
+            get_improper_states(&mdp, &policy),
+            vec![GridworldState::new(0, 0), GridworldState::new(0, 1)]
+        );
+        assert_eq!(
+            ssp_policy_evaluation(&mdp, &policy).unwrap_err(),
+            SSPError::ImproperPolicy
+        );
+    }
+
+    #[test]
+    fn no_proper_policy() {
+        let mdp = Gridworld::new(
+            vec![vec![Cell::Air, Cell::Wall, Cell::End]],
+            vec![GridworldState::new(0, 0), GridworldState::new(0, 2)],
+            vec![GridworldAction::Left, GridworldAction::Right],
+        );
+        assert_eq!(
+            ssp_value_iteration(&mdp).unwrap_err(),
+            SSPError::NoProperPolicy
+        );
+    }
+
+    #[test]
+    fn unbounded_return() {
+        let maze = get_maze();
+        let features = OneHot::new(maze.get_states());
+        let rewards = vec![1.0; features.get_num_features()];
+        let mdp = LinearRewardMDP::new(&maze, &features, &rewards);
+        assert_eq!(
+            ssp_value_iteration(&mdp).unwrap_err(),
+            SSPError::UnboundedReturn
+        );
+    }
+}
@@ -1,46 +1,120 @@
 //! # temporal_difference
 //!
 //! The `temporal_difference` module contains the implementations of the temporal difference algorithms.
+//! The solvers only interact with the problem through the [`Environment`] trait.
+//! You can use an [`MDPEnvironment`](crate::models::MDPEnvironment) to solve an [`MDP`](crate::models::MDP).
+//! The states do not need to be known in advance:
+//! the action value of a state is created the first time it is visited,
+//! with each action mapped to the initial value of the exploration strategy.
 
-use crate::models::{ActionValue, Config, MDP};
+use crate::models::{
+    Action, ActionValue, Config, Environment, Episode, Exploration, PolicyAndActionValue,
+    ReplayBuffer, Sampler, State, Transition,
+};
 
-fn sarsa_q_learning<M>(
-    mdp: &M,
+/// Moves the value of the given state-action pair towards the reward
+/// plus the discounted value of the next state given by the bootstrap function,
+/// and returns the temporal difference error.
+/// When the episode is done, the next state is terminal and its value is zero,
+/// so that the bootstrap function is not called.
+fn td_update<S, A, F>(
+    action_value: &mut ActionValue<S, A>,
     config: &Config,
+    state: &S,
+    action: &A,
+    reward: f64,
+    done: bool,
+    bootstrap: F,
+) -> f64
+where
+    S: State,
+    A: Action,
+    F: FnOnce(&ActionValue<S, A>) -> f64,
+{
+    let next_value = if done { 0.0 } else { bootstrap(action_value) };
+    let current = action_value.get(state, action);
+    let td_error = reward + config.discount_factor * next_value - current;
+    action_value.insert(state, action, current + config.learning_rate * td_error);
+    td_error
+}
+
+/// Applies the Q-Learning update rule for the given transition
+/// and returns the temporal difference error.
+pub(crate) fn q_learning_update<S, A>(
+    action_value: &mut ActionValue<S, A>,
+    config: &Config,
+    transition: &Transition<S, A>,
+) -> f64
+where
+    S: State,
+    A: Action,
+{
+    let Transition {
+        state,
+        action,
+        reward,
+        next_state,
+        done,
+    } = transition;
+    td_update(
+        action_value,
+        config,
+        state,
+        action,
+        *reward,
+        *done,
+        |action_value| action_value.get(next_state, action_value.greedy(next_state)),
+    )
+}
+
+fn sarsa_q_learning<E, X>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
     q_learning: bool,
-) -> ActionValue<M::State, M::Action>
+) -> ActionValue<E::State, E::Action>
 where
-    M: MDP,
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
 {
-    let states = mdp.get_states();
-    let actions = mdp.get_actions();
-    let mut action_value = ActionValue::new(states, actions);
+    let initial_value = exploration.initial_value();
+    let mut action_value = ActionValue::empty();
     for _ in 0..config.num_episodes {
-        let mut state = states.get_random().clone();
-        let mut action = action_value
-            .epsilon_greedy(actions, &state, config.exploration_rate)
-            .clone();
+        let mut state = env.reset();
+        let mut action = exploration.choose(
+            &state,
+            action_value.get_or_insert_state_action_value(&state, env.get_actions(), initial_value),
+            env.get_actions(),
+        );
         for _ in 0..config.max_num_steps {
-            let (next_state, reward) = mdp.transition(&state, &action);
-            let next_action = action_value
-                .epsilon_greedy(actions, &next_state, config.exploration_rate)
-                .clone();
-            // update action value
-            let current = action_value.get(&state, &action);
-            let q_value = if q_learning {
-                action_value.get(&next_state, action_value.greedy(&next_state))
-            } else {
-                action_value.get(&next_state, &next_action)
-            };
-            let target = reward + config.discount_factor * q_value;
-            action_value.insert(
+            let (next_state, reward, done, truncated) = env.step(&action);
+            let next_action = exploration.choose(
+                &next_state,
+                action_value.get_or_insert_state_action_value(
+                    &next_state,
+                    env.get_actions(),
+                    initial_value,
+                ),
+                env.get_actions(),
+            );
+            td_update(
+                &mut action_value,
+                config,
                 &state,
                 &action,
-                current + config.learning_rate * (target - current),
+                reward,
+                done,
+                |action_value| {
+                    if q_learning {
+                        action_value.get(&next_state, action_value.greedy(&next_state))
+                    } else {
+                        action_value.get(&next_state, &next_action)
+                    }
+                },
             );
             state = next_state;
             action = next_action;
-            if mdp.is_state_terminal(&state) {
+            if done || truncated {
                 break;
             }
         }
@@ -54,13 +128,21 @@ where
 /// It works by using the Bellman equation to iteratively update the action value.
 /// The algorithm stops after the given number of episodes.
 /// An episode is a sequence of state-action pairs that ends in a terminal state.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
 /// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
-/// The algorithm uses the epsilon-greedy policy to select actions.
-pub fn sarsa<M>(mdp: &M, config: &Config) -> ActionValue<M::State, M::Action>
+/// The algorithm uses the given exploration strategy to select actions.
+/// The returned action value is defined on the visited states.
+pub fn sarsa<E, X>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+) -> ActionValue<E::State, E::Action>
 where
-    M: MDP,
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
 {
-    sarsa_q_learning(mdp, config, false)
+    sarsa_q_learning(env, config, exploration, false)
 }
 
 /// # Q-Learning
@@ -69,27 +151,208 @@ where
 /// It works by using the Bellman equation to iteratively update the action value.
 /// The algorithm stops after the given number of episodes.
 /// An episode is a sequence of state-action pairs that ends in a terminal state.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
 /// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
-/// The algorithm uses the epsilon-greedy policy to select actions.
+/// The algorithm uses the given exploration strategy to select actions.
+/// The returned action value is defined on the visited states.
 /// Unlike SARSA, Q-Learning uses the greedy policy to select the action
 /// from which the value is used in the update rule.
-pub fn q_learning<M>(mdp: &M, config: &Config) -> ActionValue<M::State, M::Action>
+pub fn q_learning<E, X>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+) -> ActionValue<E::State, E::Action>
 where
-    M: MDP,
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
 {
-    sarsa_q_learning(mdp, config, true)
+    sarsa_q_learning(env, config, exploration, true)
+}
+
+/// # Soft Q-Learning
+///
+/// This function implements the soft Q-Learning algorithm,
+/// the temporal difference counterpart of
+/// [`soft_value_iteration`](crate::solvers::dynamic_programming::soft_value_iteration).
+/// It works like Q-Learning, except that the value of the next state
+/// is the log-sum-exp of its action values with the `temperature` parameter in the config
+/// instead of their maximum,
+/// which learns the action value of the entropy-regularized problem.
+/// The algorithm stops after the given number of episodes.
+/// Each episode starts by resetting the environment
+/// and ends when it is done or truncated.
+/// The number of steps per episode is limited by the `max_num_steps` parameter in the config.
+/// The algorithm uses the given exploration strategy to select actions.
+/// Returns the Boltzmann policy of the learned action value, with the same temperature,
+/// along with the action value, both defined on the visited states.
+pub fn soft_q_learning<E, X>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+) -> PolicyAndActionValue<E::State, E::Action>
+where
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
+{
+    let initial_value = exploration.initial_value();
+    let mut action_value = ActionValue::empty();
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = exploration.choose(
+                &state,
+                action_value.get_or_insert_state_action_value(
+                    &state,
+                    env.get_actions(),
+                    initial_value,
+                ),
+                env.get_actions(),
+            );
+            let (next_state, reward, done, truncated) = env.step(&action);
+            action_value.get_or_insert_state_action_value(
+                &next_state,
+                env.get_actions(),
+                initial_value,
+            );
+            td_update(
+                &mut action_value,
+                config,
+                &state,
+                &action,
+                reward,
+                done,
+                |action_value| {
+                    action_value
+                        .get_state_action_value(&next_state)
+                        .log_sum_exp(config.temperature)
+                },
+            );
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    let states = Sampler::new(action_value.get_states());
+    let policy = action_value.boltzmann_policy(&states, env.get_actions(), config.temperature);
+    (policy, action_value)
+}
+
+/// # Q-Learning Replay
+///
+/// This function replays the given episodes with the Q-Learning update rule.
+/// It can be used to learn from recorded experience,
+/// for instance captured with a [`Recorder`](crate::models::Recorder),
+/// without interacting with the environment.
+/// Each transition is replayed once, in order.
+/// Since Q-Learning is off-policy, the episodes can come from any policy.
+pub fn q_learning_replay<S, A>(
+    action_value: &mut ActionValue<S, A>,
+    config: &Config,
+    episodes: &[Episode<S, A>],
+) where
+    S: State,
+    A: Action,
+{
+    for episode in episodes {
+        for transition in episode {
+            q_learning_update(action_value, config, transition);
+        }
+    }
+}
+
+/// # Q-Learning with Experience Replay
+///
+/// This function implements the Q-Learning algorithm with an experience replay buffer.
+/// Instead of learning from each transition once, as it is experienced,
+/// every transition is stored in the buffer
+/// and the action value is updated from minibatches sampled from it.
+/// After each step, `replay_ratio` minibatches of `batch_size` transitions are replayed.
+/// The temporal difference errors of the replayed transitions
+/// are given back to the buffer to update their priorities.
+/// The algorithm uses the given exploration strategy to select actions.
+/// The returned action value is defined on the visited states.
+pub fn q_learning_with_replay<E, X, B>(
+    env: &mut E,
+    config: &Config,
+    exploration: &mut X,
+    buffer: &mut B,
+) -> ActionValue<E::State, E::Action>
+where
+    E: Environment,
+    X: Exploration<E::State, E::Action>,
+    B: ReplayBuffer<E::State, E::Action>,
+{
+    let initial_value = exploration.initial_value();
+    let mut action_value = ActionValue::empty();
+    for _ in 0..config.num_episodes {
+        let mut state = env.reset();
+        for _ in 0..config.max_num_steps {
+            let action = exploration.choose(
+                &state,
+                action_value.get_or_insert_state_action_value(
+                    &state,
+                    env.get_actions(),
+                    initial_value,
+                ),
+                env.get_actions(),
+            );
+            let (next_state, reward, done, truncated) = env.step(&action);
+            // the next state must be known before any transition to it is replayed
+            action_value.get_or_insert_state_action_value(
+                &next_state,
+                env.get_actions(),
+                initial_value,
+            );
+            buffer.push(Transition {
+                state,
+                action,
+                reward,
+                next_state: next_state.clone(),
+                done,
+            });
+            for _ in 0..config.replay_ratio {
+                let (indices, td_errors): (Vec<usize>, Vec<f64>) = buffer
+                    .sample(config.batch_size)
+                    .into_iter()
+                    .map(|(index, transition)| {
+                        (
+                            index,
+                            q_learning_update(&mut action_value, config, &transition),
+                        )
+                    })
+                    .unzip();
+                buffer.update_priorities(&indices, &td_errors);
+            }
+            state = next_state;
+            if done || truncated {
+                break;
+            }
+        }
+    }
+    action_value
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::environments::gridworld::{assert_policy_optimal, get_gridworld, get_test_config};
+    use crate::environments::gridworld::{
+        assert_policy_optimal, get_gridworld, get_test_config, Cell, Gridworld, GridworldAction,
+        GridworldState,
+    };
+    use crate::models::{
+        Boltzmann, EpsilonGreedy, MDPEnvironment, OptimisticInitialValues, PrioritizedReplay,
+        Recorder, UniformReplay, MDP, UCB,
+    };
 
     #[test]
     fn test_sarsa() {
         let mdp = get_gridworld();
         let config = get_test_config();
-        let action_value = sarsa(&mdp, &config);
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value = sarsa(&mut env, &config, &mut exploration);
         let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
         assert_policy_optimal(&policy);
     }
@@ -98,8 +361,200 @@ mod tests {
     fn test_q_learning() {
         let mdp = get_gridworld();
         let config = get_test_config();
-        let action_value = q_learning(&mdp, &config);
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value = q_learning(&mut env, &config, &mut exploration);
         let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
         assert_policy_optimal(&policy);
     }
+
+    #[test]
+    fn test_q_learning_unreachable_state() {
+        // the top right cell is walled off from the single start state
+        let mdp = Gridworld::new(
+            vec![
+                vec![Cell::Air, Cell::Air, Cell::Wall, Cell::Air],
+                vec![Cell::Wall, Cell::End, Cell::Wall, Cell::Wall],
+            ],
+            vec![
+                GridworldState::new(0, 0),
+                GridworldState::new(0, 1),
+                GridworldState::new(0, 3),
+                GridworldState::new(1, 1),
+            ],
+            vec![
+                GridworldAction::Down,
+                GridworldAction::Left,
+                GridworldAction::Right,
+                GridworldAction::Up,
+            ],
+        )
+        .initial_states(vec![GridworldState::new(0, 0)]);
+        let config = get_test_config();
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value = q_learning(&mut env, &config, &mut exploration);
+        assert_eq!(action_value.get_states().len(), 3);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_eq!(
+            policy.get(&GridworldState::new(0, 0)),
+            &GridworldAction::Right
+        );
+        assert_eq!(
+            policy.get(&GridworldState::new(0, 1)),
+            &GridworldAction::Down
+        );
+    }
+
+    /// A corridor whose states are only discovered by playing forward,
+    /// ending with a reward when the given length is reached.
+    struct Corridor {
+        length: usize,
+        position: usize,
+        actions: Sampler<GridworldAction>,
+    }
+
+    impl Environment for Corridor {
+        type State = GridworldState;
+        type Action = GridworldAction;
+
+        fn get_actions(&self) -> &Sampler<Self::Action> {
+            &self.actions
+        }
+
+        fn reset(&mut self) -> Self::State {
+            self.position = 0;
+            GridworldState::new(0, 0)
+        }
+
+        fn step(&mut self, action: &Self::Action) -> (Self::State, f64, bool, bool) {
+            if *action == GridworldAction::Right {
+                self.position += 1;
+            } else {
+                self.position = self.position.saturating_sub(1);
+            }
+            let done = self.position == self.length;
+            let reward = if done { 100.0 } else { -1.0 };
+            (GridworldState::new(0, self.position), reward, done, false)
+        }
+    }
+
+    #[test]
+    fn test_q_learning_unknown_states() {
+        let mut env = Corridor {
+            length: 5,
+            position: 0,
+            actions: Sampler::new(vec![GridworldAction::Left, GridworldAction::Right]),
+        };
+        let config = get_test_config();
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let action_value = q_learning(&mut env, &config, &mut exploration);
+        assert_eq!(action_value.get_states().len(), 6);
+        for position in 0..5 {
+            assert_eq!(
+                action_value.greedy(&GridworldState::new(0, position)),
+                &GridworldAction::Right
+            );
+        }
+    }
+
+    #[test]
+    fn terminal_states_are_not_bootstrapped() {
+        // a single step to the end, where the optimistic value of the terminal state
+        // would otherwise be bootstrapped from
+        let config = get_test_config().num_episodes(1);
+        let expected = 200.0 + config.learning_rate * (100.0 - 200.0);
+        for q_learning in [false, true] {
+            let mut env = Corridor {
+                length: 1,
+                position: 0,
+                actions: Sampler::new(vec![GridworldAction::Right]),
+            };
+            let mut exploration = OptimisticInitialValues::new(200.0);
+            let action_value = sarsa_q_learning(&mut env, &config, &mut exploration, q_learning);
+            let value = action_value.get(&GridworldState::new(0, 0), &GridworldAction::Right);
+            assert!((value - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_q_learning_replay() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = EpsilonGreedy::new(1.0);
+        let mut recorder = Recorder::new(MDPEnvironment::new(&mdp));
+        sarsa(&mut recorder, &config, &mut exploration);
+        let mut action_value = ActionValue::new(mdp.get_states(), mdp.get_actions());
+        q_learning_replay(&mut action_value, &config, recorder.get_episodes());
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_q_learning_with_uniform_replay() {
+        let mdp = get_gridworld();
+        let config = get_test_config().num_episodes(100).batch_size(4);
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let mut buffer = UniformReplay::new(100);
+        let action_value = q_learning_with_replay(&mut env, &config, &mut exploration, &mut buffer);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_q_learning_with_prioritized_replay() {
+        let mdp = get_gridworld();
+        let config = get_test_config().num_episodes(100).replay_ratio(2);
+        let mut exploration = EpsilonGreedy::new(config.exploration_rate);
+        let mut env = MDPEnvironment::new(&mdp);
+        let mut buffer = PrioritizedReplay::new(100, 0.6);
+        let action_value = q_learning_with_replay(&mut env, &config, &mut exploration, &mut buffer);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_sarsa_boltzmann() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = Boltzmann::new(1.0);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value = sarsa(&mut env, &config, &mut exploration);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_q_learning_ucb() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = UCB::new(2.0);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value = q_learning(&mut env, &config, &mut exploration);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_q_learning_optimistic_initial_values() {
+        let mdp = get_gridworld();
+        let config = get_test_config();
+        let mut exploration = OptimisticInitialValues::new(200.0);
+        let mut env = MDPEnvironment::new(&mdp);
+        let action_value = q_learning(&mut env, &config, &mut exploration);
+        let policy = action_value.greedy_policy(mdp.get_states(), mdp.get_actions());
+        assert_policy_optimal(&policy);
+    }
+
+    #[test]
+    fn test_soft_q_learning() {
+        let mdp = get_gridworld();
+        let config = get_test_config().temperature(0.1);
+        let mut exploration = Boltzmann::new(1.0);
+        let mut env = MDPEnvironment::new(&mdp);
+        let (policy, action_value) = soft_q_learning(&mut env, &config, &mut exploration);
+        assert_policy_optimal(&policy.most_likely_policy(mdp.get_states(), mdp.get_actions()));
+        assert_policy_optimal(&action_value.greedy_policy(mdp.get_states(), mdp.get_actions()));
+    }
 }